@@ -1,10 +1,32 @@
 use std::ops::{Add, Mul};
 
+use crate::error::CircuitError;
 use crate::utils::*;
 
 impl Circuit {
-    pub fn new(layers: Vec<CircuitLayer>, num_inputs: usize) -> Self {
-        Self { layers, num_inputs }
+    /// Create a new `Circuit`, checking that every gate reads values of the layer below it.
+    pub fn new(layers: Vec<CircuitLayer>, num_inputs: usize) -> Result<Self, CircuitError> {
+        let circuit = Self { layers, num_inputs };
+        circuit.validate()?;
+        Ok(circuit)
+    }
+
+    /// The checks of [`Self::new`], for circuits built directly from their public fields.
+    pub fn validate(&self) -> Result<(), CircuitError> {
+        if self.layers.is_empty() {
+            return Err(CircuitError::NoLayers);
+        }
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            let next_len = self.layers.get(i + 1).map_or(self.num_inputs, CircuitLayer::len);
+            for (gate, g) in layer.layer.iter().enumerate() {
+                if let Some(&input) = g.inputs.iter().find(|&&input| input >= next_len) {
+                    return Err(CircuitError::GateInputOutOfRange { layer: i, gate, input });
+                }
+            }
+        }
+
+        Ok(())
     }
 
     pub fn num_vars_at(&self, layer: usize) -> Option<usize> {
@@ -16,14 +38,22 @@ impl Circuit {
             return None;
         };
 
-        Some((num_gates as u64).trailing_zeros() as usize)
+        // Layers that are not a power of two are padded with zero gates
+        Some(num_gates.next_power_of_two().trailing_zeros() as usize)
     }
 
     /// Evaluate a `Circuit` on a given input.
-    pub fn evaluate<F>(&self, input: &[F]) -> CircuitEvaluation<F>
+    pub fn evaluate<F>(&self, input: &[F]) -> Result<CircuitEvaluation<F>, CircuitError>
     where
         F: Add<Output = F> + Mul<Output = F> + Copy,
     {
+        if input.len() != self.num_inputs {
+            return Err(CircuitError::WrongNumberOfInputs {
+                expected: self.num_inputs,
+                actual: input.len(),
+            });
+        }
+
         let mut layers = vec![];
         let mut current_input = input;
 
@@ -44,7 +74,7 @@ impl Circuit {
         }
 
         layers.reverse();
-        CircuitEvaluation { layers }
+        Ok(CircuitEvaluation { layers })
     }
 
     /// The $\text{add}_i(a, b, c)$ polynomial value at layer $i$.
@@ -61,6 +91,35 @@ impl Circuit {
         gate.g_type == GateType::Mul && gate.inputs[0] == b && gate.inputs[1] == c
     }
 
+    /// The sparse multilinear extension of $\text{add}_i$.
+    pub fn add_i_mle(&self, i: usize) -> WiringPredicate {
+        self.wiring_predicate(i, GateType::Add)
+    }
+
+    /// The sparse multilinear extension of $\text{mul}_i$.
+    pub fn mul_i_mle(&self, i: usize) -> WiringPredicate {
+        self.wiring_predicate(i, GateType::Mul)
+    }
+
+    fn wiring_predicate(&self, i: usize, g_type: GateType) -> WiringPredicate {
+        let a_vars = self.num_vars_at(i).expect("layer out of range");
+        let bc_vars = self.num_vars_at(i + 1).expect("layer out of range");
+
+        let entries = self.layers[i]
+            .layer
+            .iter()
+            .enumerate()
+            .filter(|(_, gate)| gate.g_type == g_type)
+            .map(|(a, gate)| (a, gate.inputs[0], gate.inputs[1]))
+            .collect();
+
+        WiringPredicate {
+            a_vars,
+            bc_vars,
+            entries,
+        }
+    }
+
     pub fn layers(&self) -> &[CircuitLayer] {
         &self.layers
     }
@@ -72,6 +131,166 @@ impl Circuit {
     pub fn num_inputs(&self) -> usize {
         self.num_inputs
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fiat_shamir_transcript::absorb::Absorb;
+    use ark_test_curves::bls12_381::Fr;
+
+    type F = Fr;
+
+    fn create_test_circuit() -> Circuit {
+        Circuit::new(
+            vec![
+                CircuitLayer::new(vec![Gate::new(GateType::Add, [0, 1]), Gate::new(GateType::Mul, [1, 0])]),
+                CircuitLayer::new(vec![Gate::new(GateType::Mul, [0, 1]), Gate::new(GateType::Add, [2, 3])]),
+            ],
+            4,
+        )
+        .unwrap()
+    }
+
+    fn to_bits(value: usize, num_bits: usize) -> Vec<F> {
+        (0..num_bits)
+            .rev()
+            .map(|j| F::from(((value >> j) & 1) as u64))
+            .collect()
+    }
+
+    #[test]
+    fn test_wiring_mle_matches_predicates() {
+        let circuit = create_test_circuit();
+
+        for layer in 0..circuit.layers.len() {
+            let add = circuit.add_i_mle(layer);
+            let mul = circuit.mul_i_mle(layer);
+
+            for a in 0..1 << add.a_vars {
+                for b in 0..1 << add.bc_vars {
+                    for c in 0..1 << add.bc_vars {
+                        let (r_a, r_b, r_c) = (to_bits(a, add.a_vars), to_bits(b, add.bc_vars), to_bits(c, add.bc_vars));
+
+                        assert_eq!(add.evaluate::<F>(&r_a, &r_b, &r_c).unwrap() == F::from(1), circuit.add_i(layer, a, b, c));
+                        assert_eq!(mul.evaluate::<F>(&r_a, &r_b, &r_c).unwrap() == F::from(1), circuit.mul_i(layer, a, b, c));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_num_vars_at_pads_to_power_of_two() {
+        let circuit = Circuit::new(
+            vec![CircuitLayer::new(vec![
+                Gate::new(GateType::Add, [0, 1]),
+                Gate::new(GateType::Mul, [1, 2]),
+                Gate::new(GateType::Add, [2, 0]),
+            ])],
+            3,
+        )
+        .unwrap();
+
+        assert_eq!(circuit.num_vars_at(0), Some(2));
+        assert_eq!(circuit.num_vars_at(1), Some(2));
+        assert_eq!(circuit.num_vars_at(2), None);
+    }
+
+    #[test]
+    fn test_layer_mles() {
+        let circuit = Circuit::new(
+            vec![CircuitLayer::new(vec![
+                Gate::new(GateType::Add, [0, 1]),
+                Gate::new(GateType::Mul, [1, 2]),
+                Gate::new(GateType::Add, [2, 0]),
+            ])],
+            3,
+        )
+        .unwrap();
+        let evaluation = circuit.evaluate(&[F::from(2), F::from(3), F::from(4)]).unwrap();
+
+        let output = evaluation.layer_mle(0);
+        assert_eq!(output.poly.variables, circuit.num_vars_at(0).unwrap());
+        assert_eq!(output.poly.evaluations, vec![F::from(5), F::from(12), F::from(6), F::from(0)]);
+        assert_eq!(output.padding, 1);
+        assert_eq!(output.len(), 3);
+
+        let layers = evaluation.layer_mles();
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0], output);
+        assert_eq!(layers[1].poly.evaluations, vec![F::from(2), F::from(3), F::from(4), F::from(0)]);
+
+        let one_gate = LayerPolynomial::new(&[F::from(7)]);
+        assert_eq!(one_gate.poly.variables, 0);
+        assert_eq!(one_gate.padding, 0);
+    }
+
+    #[test]
+    fn test_fix_a_matches_evaluate() {
+        let circuit = create_test_circuit();
+        let add = circuit.add_i_mle(1);
+
+        let r_a = vec![F::from(7)];
+        let r_b = vec![F::from(4), F::from(9)];
+        let r_c = vec![F::from(13), F::from(6)];
+        let bc_point: Vec<F> = r_b.iter().chain(&r_c).cloned().collect();
+
+        assert_eq!(add.fix_a(&r_a).unwrap().eval_full(&bc_point), add.evaluate(&r_a, &r_b, &r_c).unwrap());
+    }
 
+    #[test]
+    fn test_wiring_predicate_errors() {
+        let circuit = create_test_circuit();
+        let mut add = circuit.add_i_mle(1);
+        let (r_a, r_b) = (vec![F::from(7)], vec![F::from(4), F::from(9)]);
 
-}
\ No newline at end of file
+        assert_eq!(
+            add.evaluate(&r_a, &r_b, &r_a),
+            Err(CircuitError::WrongNumberOfVariables { expected: 2, actual: 1 })
+        );
+        assert_eq!(add.fix_a(&r_b), Err(CircuitError::WrongNumberOfVariables { expected: 1, actual: 2 }));
+
+        add.entries.push((0, 4, 1));
+        assert_eq!(add.evaluate(&r_a, &r_b, &r_b), Err(CircuitError::WiringEntryOutOfRange { a: 0, b: 4, c: 1 }));
+        assert_eq!(add.fix_a(&r_a), Err(CircuitError::WiringEntryOutOfRange { a: 0, b: 4, c: 1 }));
+    }
+
+    #[test]
+    fn test_new_rejects_invalid_circuits() {
+        assert!(matches!(Circuit::new(vec![], 2), Err(CircuitError::NoLayers)));
+
+        let layers = vec![
+            CircuitLayer::new(vec![Gate::new(GateType::Add, [0, 1])]),
+            CircuitLayer::new(vec![Gate::new(GateType::Mul, [0, 1]), Gate::new(GateType::Add, [1, 3])]),
+        ];
+        assert!(matches!(
+            Circuit::new(layers, 3),
+            Err(CircuitError::GateInputOutOfRange { layer: 1, gate: 1, input: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_wrong_number_of_inputs() {
+        let circuit = create_test_circuit();
+
+        assert!(matches!(
+            circuit.evaluate(&[F::from(1), F::from(2), F::from(3)]),
+            Err(CircuitError::WrongNumberOfInputs { expected: 4, actual: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_transcript_bytes_bind_the_wiring() {
+        let circuit = create_test_circuit();
+        let mut layers = circuit.layers.clone();
+        layers[0].layer[0] = Gate::new(GateType::Add, [1, 0]);
+        let rewired = Circuit::new(layers, 4).unwrap();
+
+        // Both compute the same outputs on every input
+        let input = [F::from(1), F::from(2), F::from(3), F::from(4)];
+        assert_eq!(circuit.evaluate(&input).unwrap().layers[0], rewired.evaluate(&input).unwrap().layers[0]);
+        assert_ne!(circuit.to_transcript_bytes(), rewired.to_transcript_bytes());
+        assert_ne!(circuit.to_transcript_bytes(), Circuit::new(circuit.layers.clone(), 5).unwrap().to_transcript_bytes());
+    }
+}
@@ -0,0 +1,41 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CircuitError {
+    /// A circuit needs at least its output layer.
+    NoLayers,
+
+    /// A gate reads a value the layer below it does not have.
+    GateInputOutOfRange { layer: usize, gate: usize, input: usize },
+
+    /// The circuit was evaluated on the wrong number of inputs.
+    WrongNumberOfInputs { expected: usize, actual: usize },
+
+    /// A wiring predicate was evaluated at a point with the wrong number of variables.
+    WrongNumberOfVariables { expected: usize, actual: usize },
+
+    /// An entry of a wiring predicate labels a gate outside of its layer.
+    WiringEntryOutOfRange { a: usize, b: usize, c: usize },
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoLayers => write!(f, "a circuit needs at least one layer"),
+            Self::GateInputOutOfRange { layer, gate, input } => {
+                write!(f, "gate {} of layer {} reads input {} which does not exist", gate, layer, input)
+            }
+            Self::WrongNumberOfInputs { expected, actual } => {
+                write!(f, "expected {} inputs, got {}", expected, actual)
+            }
+            Self::WrongNumberOfVariables { expected, actual } => {
+                write!(f, "expected a point in {} variables, got {}", expected, actual)
+            }
+            Self::WiringEntryOutOfRange { a, b, c } => {
+                write!(f, "wiring entry ({}, {}, {}) is outside of the layers", a, b, c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
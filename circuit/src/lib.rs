@@ -1,2 +1,3 @@
 pub mod utils;
-pub mod circuit;
\ No newline at end of file
+pub mod circuit;
+pub mod error;
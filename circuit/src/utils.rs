@@ -1,3 +1,9 @@
+use ark_ff::PrimeField;
+use polynomials::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
+use polynomials::multilinear_pol::eq_poly::eq_poly;
+use fiat_shamir_transcript::absorb::{write_length, Absorb};
+use crate::error::CircuitError;
+
 #[derive(Clone, Copy, PartialEq)]
 pub enum GateType {
     Add,
@@ -38,6 +44,82 @@ impl CircuitLayer {
     }
 }
 
+/// The multilinear extension of a wiring predicate ($\text{add}_i$ or $\text{mul}_i$)
+/// stored sparsely as the $(a, b, c)$ labels where it is one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WiringPredicate {
+    /// Number of variables of the `a` block.
+    pub a_vars: usize,
+
+    /// Number of variables of each of the `b` and `c` blocks.
+    pub bc_vars: usize,
+
+    pub entries: Vec<(usize, usize, usize)>,
+}
+
+impl WiringPredicate {
+    /// Evaluates the predicate at $(r_a, r_b, r_c)$ in time linear in the
+    /// number of gates of the layer and of the layer below it.
+    pub fn evaluate<F: PrimeField>(&self, r_a: &[F], r_b: &[F], r_c: &[F]) -> Result<F, CircuitError> {
+        for (point, expected) in [(r_a, self.a_vars), (r_b, self.bc_vars), (r_c, self.bc_vars)] {
+            check_num_variables(point, expected)?;
+        }
+        self.check_entries()?;
+
+        let eq_a = eq_poly(r_a).evaluations;
+        let eq_b = eq_poly(r_b).evaluations;
+        let eq_c = eq_poly(r_c).evaluations;
+
+        Ok(self
+            .entries
+            .iter()
+            .map(|&(a, b, c)| eq_a[a] * eq_b[b] * eq_c[c])
+            .sum())
+    }
+
+    /// Fixes the `a` block at `r_a`, leaving a polynomial over $(b, c)$
+    /// with `b` as the most significant block.
+    pub fn fix_a<F: PrimeField>(&self, r_a: &[F]) -> Result<MultiLinearPolynomial<F>, CircuitError> {
+        check_num_variables(r_a, self.a_vars)?;
+        self.check_entries()?;
+
+        let eq_a = eq_poly(r_a).evaluations;
+        let mut evaluations = vec![F::zero(); 1 << (2 * self.bc_vars)];
+
+        for &(a, b, c) in &self.entries {
+            evaluations[(b << self.bc_vars) | c] += eq_a[a];
+        }
+
+        Ok(MultiLinearPolynomial::new(2 * self.bc_vars, evaluations))
+    }
+
+    // The fields are public, so the labels may not fit in the blocks
+    fn check_entries(&self) -> Result<(), CircuitError> {
+        match self
+            .entries
+            .iter()
+            .find(|&&(a, b, c)| !fits(a, self.a_vars) || !fits(b, self.bc_vars) || !fits(c, self.bc_vars))
+        {
+            Some(&(a, b, c)) => Err(CircuitError::WiringEntryOutOfRange { a, b, c }),
+            None => Ok(()),
+        }
+    }
+}
+
+// Whether `label` is a point of the hypercube in `vars` variables
+fn fits(label: usize, vars: usize) -> bool {
+    label.checked_shr(vars as u32).unwrap_or(0) == 0
+}
+
+fn check_num_variables<F>(point: &[F], expected: usize) -> Result<(), CircuitError> {
+    if point.len() != expected {
+        return Err(CircuitError::WrongNumberOfVariables {
+            expected,
+            actual: point.len(),
+        });
+    }
+    Ok(())
+}
 
 pub struct CircuitEvaluation<F> {
     /// Evaluations on per-layer basis.
@@ -51,6 +133,50 @@ impl<F: Copy> CircuitEvaluation<F> {
     }
 }
 
+impl<F: PrimeField> CircuitEvaluation<F> {
+    /// The multilinear extension of the gate values at layer `layer`.
+    pub fn layer_mle(&self, layer: usize) -> LayerPolynomial<F> {
+        LayerPolynomial::new(&self.layers[layer])
+    }
+
+    /// The multilinear extensions of all layers, output layer first.
+    pub fn layer_mles(&self) -> Vec<LayerPolynomial<F>> {
+        self.layers.iter().map(|layer| LayerPolynomial::new(layer)).collect()
+    }
+}
+
+/// The values of a layer as a multilinear polynomial.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerPolynomial<F: PrimeField> {
+    pub poly: MultiLinearPolynomial<F>,
+
+    /// Number of zeros appended to the layer values to reach a power of two.
+    pub padding: usize,
+}
+
+impl<F: PrimeField> LayerPolynomial<F> {
+    /// Create a new `LayerPolynomial`, padding `values` with zeros up to the next power of two.
+    pub fn new(values: &[F]) -> Self {
+        let padded_len = values.len().next_power_of_two();
+        let mut evaluations = values.to_vec();
+        evaluations.resize(padded_len, F::zero());
+
+        Self {
+            poly: MultiLinearPolynomial::new(padded_len.trailing_zeros() as usize, evaluations),
+            padding: padded_len - values.len(),
+        }
+    }
+
+    /// Number of values of the layer before padding.
+    pub fn len(&self) -> usize {
+        self.poly.evaluations.len() - self.padding
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 /// The circuit in layered form.
 #[derive(Clone)]
 pub struct Circuit {
@@ -61,3 +187,29 @@ pub struct Circuit {
     /// Number of inputs
     pub num_inputs: usize,
 }
+
+// The gate type as one byte, then the two input labels
+impl Absorb for Gate {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.push(match self.g_type {
+            GateType::Add => 0,
+            GateType::Mul => 1,
+        });
+        write_length(self.inputs[0], bytes);
+        write_length(self.inputs[1], bytes);
+    }
+}
+
+impl Absorb for CircuitLayer {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        self.layer.as_slice().write_transcript_bytes(bytes);
+    }
+}
+
+// The number of inputs, then the layers from the output layer down
+impl Absorb for Circuit {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        write_length(self.num_inputs, bytes);
+        self.layers.as_slice().write_transcript_bytes(bytes);
+    }
+}
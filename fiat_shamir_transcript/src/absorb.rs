@@ -0,0 +1,67 @@
+use ark_ff::{BigInteger, PrimeField};
+
+/// Values with a canonical encoding for transcripts, so that the prover and the
+/// verifier bind exactly the same bytes. Field elements are written as fixed size
+/// big-endian bytes, and every variable length sequence is prefixed by its length.
+pub trait Absorb {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>);
+
+    fn to_transcript_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_transcript_bytes(&mut bytes);
+        bytes
+    }
+}
+
+impl<F: PrimeField> Absorb for F {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.into_bigint().to_bytes_be());
+    }
+}
+
+impl<T: Absorb> Absorb for [T] {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        write_length(self.len(), bytes);
+        for item in self {
+            item.write_transcript_bytes(bytes);
+        }
+    }
+}
+
+/// Writes the length prefix of a sequence, as 8 little-endian bytes.
+pub fn write_length(len: usize, bytes: &mut Vec<u8>) {
+    bytes.extend_from_slice(&(len as u64).to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr;
+
+    #[test]
+    fn test_field_element_encoding() {
+        let bytes = Fr::from(258).to_transcript_bytes();
+
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(&bytes[30..], &[1, 2]);
+        assert!(bytes[..30].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn test_slice_encoding_is_length_prefixed() {
+        let bytes = [Fr::from(1), Fr::from(2)].as_slice().to_transcript_bytes();
+
+        assert_eq!(bytes.len(), 8 + 2 * 32);
+        assert_eq!(&bytes[..8], &2u64.to_le_bytes());
+
+        // Splitting the same elements differently gives different encodings
+        let split = |left: &[Fr], right: &[Fr]| {
+            let mut bytes = Vec::new();
+            left.write_transcript_bytes(&mut bytes);
+            right.write_transcript_bytes(&mut bytes);
+            bytes
+        };
+        let elements = [Fr::from(1), Fr::from(2), Fr::from(3)];
+        assert_ne!(split(&elements[..1], &elements[1..]), split(&elements[..2], &elements[2..]));
+    }
+}
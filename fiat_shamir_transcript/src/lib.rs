@@ -1,2 +1,5 @@
 pub mod transcript;
-pub mod transcript_interface;
\ No newline at end of file
+pub mod transcript_interface;
+pub mod poseidon;
+pub mod absorb;
+pub mod recording;
@@ -0,0 +1,428 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use ark_ff::{BigInteger, PrimeField};
+use crate::transcript::Transcript;
+use crate::transcript_interface::TranscriptInterface;
+
+// Tags telling absorbed messages and squeezed challenges apart
+const ABSORB_OP: u64 = 0;
+const SQUEEZE_OP: u64 = 1;
+const FORK_OP: u64 = 2;
+
+/// Parameters of the Poseidon permutation over `F`.
+///
+/// The parameters are not the standard ones: the round constants come from a Keccak
+/// transcript instead of the Grain LFSR of the Poseidon reference, so the permutation does
+/// not match other Poseidon implementations, even with the same width and numbers of rounds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoseidonConfig<F: PrimeField> {
+    pub width: usize,
+    pub full_rounds: usize,
+    pub partial_rounds: usize,
+    pub alpha: u64,
+    // One vector of `width` constants per round
+    pub round_constants: Vec<Vec<F>>,
+    pub mds: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> PoseidonConfig<F> {
+    /// Generates the round constants and the MDS matrix of a Poseidon instance.
+    /// The constants are squeezed from a Keccak transcript bound to all the parameters,
+    /// and the MDS matrix is the Cauchy matrix $M_{ij} = 1 / (i + j + \text{width})$.
+    /// This runs a Keccak transcript for every constant; see [`Self::shared_default`]
+    /// to generate the default instance only once.
+    pub fn new(width: usize, full_rounds: usize, partial_rounds: usize, alpha: u64) -> Self {
+        assert!(width >= 2, "The state needs at least one rate and one capacity element");
+        assert!(full_rounds.is_multiple_of(2), "The full rounds are split evenly around the partial rounds");
+        assert!(
+            gcd(alpha, modulus_minus_one_mod(F::MODULUS, alpha)) == 1,
+            "x^alpha must be a permutation of the field"
+        );
+
+        let mut transcript = Transcript::new(b"poseidon_parameters");
+        transcript.append(b"modulus", &F::MODULUS.to_bytes_le());
+        transcript.append(b"width", &(width as u64).to_le_bytes());
+        transcript.append(b"full_rounds", &(full_rounds as u64).to_le_bytes());
+        transcript.append(b"partial_rounds", &(partial_rounds as u64).to_le_bytes());
+        transcript.append(b"alpha", &alpha.to_le_bytes());
+
+        let round_constants = (0..full_rounds + partial_rounds)
+            .map(|_| {
+                (0..width)
+                    .map(|_| transcript.sample_challenge(b"round_constant"))
+                    .collect()
+            })
+            .collect();
+
+        let mds = (0..width)
+            .map(|i| {
+                (0..width)
+                    .map(|j| {
+                        F::from((i + j + width) as u64)
+                            .inverse()
+                            .expect("Cauchy matrix entries are non-zero")
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            width,
+            full_rounds,
+            partial_rounds,
+            alpha,
+            round_constants,
+            mds,
+        }
+    }
+
+    /// The default configuration, generated once per field and then shared.
+    pub fn shared_default() -> Arc<Self> {
+        static DEFAULTS: OnceLock<Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>> = OnceLock::new();
+
+        let mut defaults = DEFAULTS.get_or_init(Default::default).lock().expect("poisoned by a panic in `default`");
+        defaults
+            .entry(TypeId::of::<F>())
+            .or_insert_with(|| Arc::new(Self::default()))
+            .clone()
+            .downcast()
+            .expect("the configurations are keyed by their field")
+    }
+
+    pub fn rate(&self) -> usize {
+        self.width - 1
+    }
+
+    /// Applies the Poseidon permutation to `state` in place.
+    pub fn permute(&self, state: &mut [F]) {
+        assert_eq!(state.len(), self.width, "State length must equal the width");
+
+        let half_full_rounds = self.full_rounds / 2;
+
+        for (round, constants) in self.round_constants.iter().enumerate() {
+            for (s, c) in state.iter_mut().zip(constants) {
+                *s += c;
+            }
+
+            let is_full_round = round < half_full_rounds || round >= half_full_rounds + self.partial_rounds;
+            if is_full_round {
+                for s in state.iter_mut() {
+                    *s = s.pow([self.alpha]);
+                }
+            } else {
+                state[0] = state[0].pow([self.alpha]);
+            }
+
+            let mixed: Vec<F> = self
+                .mds
+                .iter()
+                .map(|row| row.iter().zip(state.iter()).map(|(m, s)| *m * s).sum())
+                .collect();
+            state.copy_from_slice(&mixed);
+        }
+    }
+}
+
+impl<F: PrimeField> Default for PoseidonConfig<F> {
+    /// Width 3 with x^5, 8 full and 57 partial rounds: the instance targeting
+    /// 128-bit security over ~255-bit fields such as the BLS12-381 scalar field.
+    fn default() -> Self {
+        Self::new(3, 8, 57, 5)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpongeMode {
+    Absorbing(usize),
+    Squeezing(usize),
+}
+
+/// A duplex-sponge Fiat-Shamir transcript over the Poseidon permutation. Its state is made
+/// of field elements, the first one being the capacity, so it can be recomputed in a circuit.
+#[derive(Debug, Clone)]
+pub struct PoseidonTranscript<F: PrimeField> {
+    config: Arc<PoseidonConfig<F>>,
+    state: Vec<F>,
+    mode: SpongeMode,
+}
+
+impl<F: PrimeField> PoseidonTranscript<F> {
+    pub fn new(protocol_label: &'static [u8]) -> Self {
+        Self::new_with_config(protocol_label, PoseidonConfig::shared_default())
+    }
+
+    /// Takes the configuration either by value or already shared, as an `Arc`.
+    pub fn new_with_config(protocol_label: &'static [u8], config: impl Into<Arc<PoseidonConfig<F>>>) -> Self {
+        let config = config.into();
+        let mut transcript = Self {
+            state: vec![F::zero(); config.width],
+            config,
+            mode: SpongeMode::Absorbing(0),
+        };
+        transcript.append(b"dom-sep", protocol_label);
+        transcript
+    }
+
+    /// Appends labeled bytes, packed into field elements.
+    pub fn append(&mut self, label: &'static [u8], new_data: &[u8]) {
+        self.absorb_frame(ABSORB_OP, label, new_data.len());
+        for element in bytes_to_field_elements::<F>(new_data) {
+            self.absorb_element(element);
+        }
+    }
+
+    /// Appends labeled field elements natively, without going through bytes.
+    pub fn append_field_elements(&mut self, label: &'static [u8], elements: &[F]) {
+        self.absorb_frame(ABSORB_OP, label, elements.len());
+        for element in elements {
+            self.absorb_element(*element);
+        }
+    }
+
+    pub fn sample_field_element(&mut self, label: &'static [u8]) -> F {
+        self.sample_field_elements(label, 1)[0]
+    }
+
+    pub fn sample_field_elements(&mut self, label: &'static [u8], n: usize) -> Vec<F> {
+        self.absorb_frame(SQUEEZE_OP, label, n);
+        (0..n).map(|_| self.squeeze_element()).collect()
+    }
+
+    // Copies the sponge and binds the copy to the label and index of the child
+    pub fn fork(&self, label: &'static [u8], index: u64) -> Self {
+        let mut child = self.clone();
+        child.absorb_frame(FORK_OP, label, 1);
+        child.absorb_element(F::from(index));
+        child
+    }
+
+    // Every operation starts with op || len(label) || label || len(data),
+    // so that no two different sequences of operations absorb the same elements
+    fn absorb_frame(&mut self, op: u64, label: &[u8], data_len: usize) {
+        self.absorb_element(F::from(op));
+        self.absorb_element(F::from(label.len() as u64));
+        for element in bytes_to_field_elements::<F>(label) {
+            self.absorb_element(element);
+        }
+        self.absorb_element(F::from(data_len as u64));
+    }
+
+    fn absorb_element(&mut self, element: F) {
+        let rate = self.config.rate();
+        let index = match self.mode {
+            SpongeMode::Absorbing(index) if index < rate => index,
+            SpongeMode::Absorbing(_) => {
+                self.config.permute(&mut self.state);
+                0
+            }
+            SpongeMode::Squeezing(_) => 0,
+        };
+
+        self.state[1 + index] += element;
+        self.mode = SpongeMode::Absorbing(index + 1);
+    }
+
+    fn squeeze_element(&mut self) -> F {
+        let rate = self.config.rate();
+        let index = match self.mode {
+            SpongeMode::Squeezing(index) if index < rate => index,
+            _ => {
+                self.config.permute(&mut self.state);
+                0
+            }
+        };
+
+        self.mode = SpongeMode::Squeezing(index + 1);
+        self.state[1 + index]
+    }
+}
+
+impl<F: PrimeField> TranscriptInterface for PoseidonTranscript<F> {
+    fn new(protocol_label: &'static [u8]) -> Self {
+        PoseidonTranscript::new(protocol_label)
+    }
+
+    fn submit_data(&mut self, label: &'static [u8], input_data: &[u8]) {
+        self.append(label, input_data);
+    }
+
+    // The little-endian bytes of a squeezed field element
+    fn generate_challenge(&mut self, label: &'static [u8]) -> [u8; 32] {
+        let bytes = self.sample_field_element(label).into_bigint().to_bytes_le();
+        let mut result = [0_u8; 32];
+        let len = bytes.len().min(32);
+        result[..len].copy_from_slice(&bytes[..len]);
+        result
+    }
+
+    // Exact when `G` is the sponge field, reduced modulo the order of `G` otherwise
+    fn compute_challenge_in_field<G: PrimeField>(&mut self, label: &'static [u8]) -> G {
+        G::from_le_bytes_mod_order(&self.sample_field_element(label).into_bigint().to_bytes_le())
+    }
+
+    fn fork(&self, label: &'static [u8], index: u64) -> Self {
+        PoseidonTranscript::fork(self, label, index)
+    }
+
+    fn compute_challenges_in_field<G: PrimeField>(&mut self, label: &'static [u8], n: usize) -> Vec<G> {
+        self.sample_field_elements(label, n)
+            .into_iter()
+            .map(|element| G::from_le_bytes_mod_order(&element.into_bigint().to_bytes_le()))
+            .collect()
+    }
+}
+
+/// Packs bytes into field elements, using chunks small enough to never wrap around the modulus.
+fn bytes_to_field_elements<F: PrimeField>(bytes: &[u8]) -> Vec<F> {
+    let chunk_size = (F::MODULUS_BIT_SIZE as usize - 1) / 8;
+    bytes.chunks(chunk_size).map(F::from_le_bytes_mod_order).collect()
+}
+
+/// (p - 1) mod `n`, where p is given by its little-endian limbs.
+fn modulus_minus_one_mod<B: BigInteger>(modulus: B, n: u64) -> u64 {
+    let p_mod_n = modulus
+        .as_ref()
+        .iter()
+        .rev()
+        .fold(0u128, |acc, &limb| ((acc << 64) + limb as u128) % n as u128) as u64;
+    (p_mod_n + n - 1) % n
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::{Fq, Fr};
+
+    #[test]
+    fn test_default_config() {
+        let config = PoseidonConfig::<Fr>::default();
+
+        assert_eq!(config.round_constants.len(), 65);
+        assert!(config.round_constants.iter().all(|c| c.len() == 3));
+        assert_eq!(config, PoseidonConfig::<Fr>::default());
+    }
+
+    #[test]
+    fn test_shared_default_config() {
+        let shared = PoseidonConfig::<Fr>::shared_default();
+
+        assert!(Arc::ptr_eq(&shared, &PoseidonConfig::<Fr>::shared_default()));
+        assert_eq!(*shared, PoseidonConfig::default());
+        assert_eq!(*PoseidonConfig::<Fq>::shared_default(), PoseidonConfig::default());
+        assert!(Arc::ptr_eq(&PoseidonTranscript::<Fr>::new(b"test").config, &shared));
+
+        // A configuration given by value gives the same transcript as the shared one
+        let mut transcript_1 = PoseidonTranscript::<Fr>::new(b"test");
+        let mut transcript_2 = PoseidonTranscript::new_with_config(b"test", PoseidonConfig::<Fr>::default());
+        assert_eq!(transcript_1.sample_field_element(b"challenge"), transcript_2.sample_field_element(b"challenge"));
+    }
+
+    #[test]
+    #[should_panic(expected = "x^alpha must be a permutation of the field")]
+    fn test_invalid_alpha() {
+        // 3 divides p - 1 for the BLS12-381 scalar field
+        PoseidonConfig::<Fr>::new(3, 8, 57, 3);
+    }
+
+    #[test]
+    fn test_permutation() {
+        let config = PoseidonConfig::<Fr>::default();
+
+        let mut state_1 = vec![Fr::from(0), Fr::from(1), Fr::from(2)];
+        let mut state_2 = vec![Fr::from(0), Fr::from(1), Fr::from(3)];
+        config.permute(&mut state_1);
+        config.permute(&mut state_2);
+
+        assert_ne!(state_1, vec![Fr::from(0), Fr::from(1), Fr::from(2)]);
+        assert!(state_1.iter().zip(&state_2).all(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn test_challenges_are_deterministic() {
+        let challenge = |data: &[Fr]| {
+            let mut transcript = PoseidonTranscript::<Fr>::new(b"test");
+            transcript.append_field_elements(b"data", data);
+            transcript.sample_field_element(b"challenge")
+        };
+
+        assert_eq!(challenge(&[Fr::from(1), Fr::from(2)]), challenge(&[Fr::from(1), Fr::from(2)]));
+        assert_ne!(challenge(&[Fr::from(1), Fr::from(2)]), challenge(&[Fr::from(1), Fr::from(3)]));
+        assert_ne!(challenge(&[Fr::from(1), Fr::from(2)]), challenge(&[Fr::from(1), Fr::from(2), Fr::from(0)]));
+    }
+
+    #[test]
+    fn test_labels_separate_messages() {
+        let mut transcript_1 = PoseidonTranscript::<Fr>::new(b"test");
+        transcript_1.append_field_elements(b"first", &[Fr::from(1)]);
+
+        let mut transcript_2 = PoseidonTranscript::<Fr>::new(b"test");
+        transcript_2.append_field_elements(b"second", &[Fr::from(1)]);
+
+        assert_ne!(transcript_1.sample_field_element(b"challenge"), transcript_2.sample_field_element(b"challenge"));
+
+        let mut transcript_3 = PoseidonTranscript::<Fr>::new(b"other test");
+        transcript_3.append_field_elements(b"first", &[Fr::from(1)]);
+        let mut transcript_1 = PoseidonTranscript::<Fr>::new(b"test");
+        transcript_1.append_field_elements(b"first", &[Fr::from(1)]);
+
+        assert_ne!(transcript_1.sample_field_element(b"challenge"), transcript_3.sample_field_element(b"challenge"));
+    }
+
+    #[test]
+    fn test_consecutive_challenges_differ() {
+        let mut transcript = PoseidonTranscript::<Fr>::new(b"test");
+        let challenges: Vec<Fr> = (0..5).map(|_| transcript.compute_challenge_in_field(b"challenge")).collect();
+
+        for i in 0..challenges.len() {
+            for j in i + 1..challenges.len() {
+                assert_ne!(challenges[i], challenges[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bytes_and_elements_agree() {
+        let mut transcript_1 = PoseidonTranscript::<Fr>::new(b"test");
+        transcript_1.submit_data(b"data", &[1, 2, 3]);
+
+        // Bytes are packed little-endian into a single element
+        let mut transcript_2 = PoseidonTranscript::<Fr>::new(b"test");
+        transcript_2.absorb_frame(ABSORB_OP, b"data", 3);
+        transcript_2.absorb_element(Fr::from(0x030201));
+
+        assert_eq!(transcript_1.sample_field_element(b"challenge"), transcript_2.sample_field_element(b"challenge"));
+    }
+
+    #[test]
+    fn test_other_field() {
+        let mut transcript = PoseidonTranscript::<Fq>::new(b"test");
+        transcript.append_field_elements(b"data", &[Fq::from(1)]);
+        let challenge: Fr = transcript.compute_challenge_in_field(b"challenge");
+
+        assert_ne!(challenge, Fr::from(0));
+    }
+
+    #[test]
+    fn test_batch_and_fork() {
+        let mut transcript = PoseidonTranscript::<Fr>::new(b"test");
+        transcript.append_field_elements(b"data", &[Fr::from(1)]);
+
+        let mut child_0 = transcript.fork(b"child", 0);
+        let mut child_1 = transcript.fork(b"child", 1);
+        assert_ne!(child_0.sample_field_element(b"challenge"), child_1.sample_field_element(b"challenge"));
+
+        let batch: Vec<Fr> = transcript.compute_challenges_in_field(b"challenges", 3);
+        assert_eq!(batch.len(), 3);
+        assert!(batch[0] != batch[1] && batch[1] != batch[2]);
+    }
+}
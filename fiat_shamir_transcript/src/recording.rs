@@ -0,0 +1,288 @@
+use std::fmt;
+use ark_ff::{BigInteger, PrimeField};
+use crate::transcript::Transcript;
+use crate::transcript_interface::TranscriptInterface;
+
+const ABSORB_TAG: u8 = 0;
+const SQUEEZE_TAG: u8 = 1;
+
+/// One labeled operation on a transcript. Squeezes record the challenge they produced,
+/// as raw bytes or as the big-endian bytes of the field element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptEvent {
+    Absorb { label: Vec<u8>, data: Vec<u8> },
+    Squeeze { label: Vec<u8>, output: Vec<u8> },
+}
+
+impl fmt::Display for TranscriptEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranscriptEvent::Absorb { label, data } => {
+                write!(f, "absorb \"{}\": 0x{}", String::from_utf8_lossy(label), to_hex(data))
+            }
+            TranscriptEvent::Squeeze { label, output } => {
+                write!(f, "squeeze \"{}\": 0x{}", String::from_utf8_lossy(label), to_hex(output))
+            }
+        }
+    }
+}
+
+/// The ordered log of everything a transcript absorbed and squeezed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    pub events: Vec<TranscriptEvent>,
+}
+
+/// The first operation at which two recordings disagree; `None` on one side
+/// means that recording stopped before this operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub index: usize,
+    pub left: Option<TranscriptEvent>,
+    pub right: Option<TranscriptEvent>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let describe = |event: &Option<TranscriptEvent>| match event {
+            Some(event) => event.to_string(),
+            None => "end of recording".to_string(),
+        };
+        write!(
+            f,
+            "Transcripts diverge at operation {}:\n  left:  {}\n  right: {}",
+            self.index,
+            describe(&self.left),
+            describe(&self.right)
+        )
+    }
+}
+
+impl Recording {
+    pub fn new() -> Self {
+        Self { events: Vec::new() }
+    }
+
+    /// Compares two recordings operation by operation, e.g. the prover's and the verifier's.
+    pub fn first_divergence(&self, other: &Recording) -> Option<Divergence> {
+        let len = self.events.len().max(other.events.len());
+        (0..len)
+            .find(|&i| self.events.get(i) != other.events.get(i))
+            .map(|index| Divergence {
+                index,
+                left: self.events.get(index).cloned(),
+                right: other.events.get(index).cloned(),
+            })
+    }
+
+    /// Encodes every event as tag || len(label) || label || len(bytes) || bytes,
+    /// lengths being 8 little-endian bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for event in &self.events {
+            let (tag, label, data) = match event {
+                TranscriptEvent::Absorb { label, data } => (ABSORB_TAG, label, data),
+                TranscriptEvent::Squeeze { label, output } => (SQUEEZE_TAG, label, output),
+            };
+            bytes.push(tag);
+            bytes.extend_from_slice(&(label.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(label);
+            bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(data);
+        }
+        bytes
+    }
+
+    /// Decodes the output of `to_bytes`, returning `None` on malformed input.
+    pub fn from_bytes(mut bytes: &[u8]) -> Option<Self> {
+        let mut events = Vec::new();
+        while let Some((&tag, rest)) = bytes.split_first() {
+            let (label, rest) = read_chunk(rest)?;
+            let (data, rest) = read_chunk(rest)?;
+            let (label, data) = (label.to_vec(), data.to_vec());
+            events.push(match tag {
+                ABSORB_TAG => TranscriptEvent::Absorb { label, data },
+                SQUEEZE_TAG => TranscriptEvent::Squeeze { label, output: data },
+                _ => return None,
+            });
+            bytes = rest;
+        }
+        Some(Self { events })
+    }
+}
+
+/// Forwards every operation to the inner transcript and records it, so that the
+/// runs of a prover and a verifier can be compared when they disagree.
+#[derive(Debug, Clone)]
+pub struct RecordingTranscript<T: TranscriptInterface = Transcript> {
+    inner: T,
+    recording: Recording,
+}
+
+impl<T: TranscriptInterface> RecordingTranscript<T> {
+    /// Records the operations on `inner` from now on; what it absorbed before is not part of the recording.
+    pub fn from_transcript(inner: T) -> Self {
+        Self {
+            inner,
+            recording: Recording::new(),
+        }
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    pub fn into_inner(self) -> (T, Recording) {
+        (self.inner, self.recording)
+    }
+}
+
+impl<T: TranscriptInterface> TranscriptInterface for RecordingTranscript<T> {
+    // The protocol label is recorded as an absorb under the "dom-sep" label
+    fn new(protocol_label: &'static [u8]) -> Self {
+        let mut transcript = Self::from_transcript(T::new(protocol_label));
+        transcript.recording.events.push(TranscriptEvent::Absorb {
+            label: b"dom-sep".to_vec(),
+            data: protocol_label.to_vec(),
+        });
+        transcript
+    }
+
+    fn submit_data(&mut self, label: &'static [u8], input_data: &[u8]) {
+        self.inner.submit_data(label, input_data);
+        self.recording.events.push(TranscriptEvent::Absorb {
+            label: label.to_vec(),
+            data: input_data.to_vec(),
+        });
+    }
+
+    fn generate_challenge(&mut self, label: &'static [u8]) -> [u8; 32] {
+        let challenge = self.inner.generate_challenge(label);
+        self.recording.events.push(TranscriptEvent::Squeeze {
+            label: label.to_vec(),
+            output: challenge.to_vec(),
+        });
+        challenge
+    }
+
+    fn compute_challenge_in_field<F: PrimeField>(&mut self, label: &'static [u8]) -> F {
+        let challenge: F = self.inner.compute_challenge_in_field(label);
+        self.recording.events.push(TranscriptEvent::Squeeze {
+            label: label.to_vec(),
+            output: challenge.into_bigint().to_bytes_be(),
+        });
+        challenge
+    }
+
+    // The child starts a recording of its own with the fork
+    fn fork(&self, label: &'static [u8], index: u64) -> Self {
+        let mut child = Self::from_transcript(self.inner.fork(label, index));
+        child.recording.events.push(TranscriptEvent::Absorb {
+            label: b"fork".to_vec(),
+            data: [label, &index.to_le_bytes()[..]].concat(),
+        });
+        child
+    }
+
+    // Recorded as a single squeeze of the concatenated challenges
+    fn compute_challenges_in_field<F: PrimeField>(&mut self, label: &'static [u8], n: usize) -> Vec<F> {
+        let challenges: Vec<F> = self.inner.compute_challenges_in_field(label, n);
+        self.recording.events.push(TranscriptEvent::Squeeze {
+            label: label.to_vec(),
+            output: challenges.iter().flat_map(|c| c.into_bigint().to_bytes_be()).collect(),
+        });
+        challenges
+    }
+}
+
+// Splits a length-prefixed chunk off the front of `bytes`
+fn read_chunk(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    if bytes.len() < 8 {
+        return None;
+    }
+    let (len, rest) = bytes.split_at(8);
+    let len = usize::try_from(u64::from_le_bytes(len.try_into().ok()?)).ok()?;
+    if rest.len() < len {
+        return None;
+    }
+    Some(rest.split_at(len))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr;
+
+    fn run(data: &[u8]) -> (Fr, Recording) {
+        let mut transcript = RecordingTranscript::<Transcript>::new(b"test");
+        transcript.submit_data(b"first", &[1, 2]);
+        transcript.submit_data(b"second", data);
+        let challenge = transcript.compute_challenge_in_field(b"challenge");
+        (challenge, transcript.recording().clone())
+    }
+
+    #[test]
+    fn test_recording_does_not_change_challenges() {
+        let (challenge, recording) = run(&[3]);
+
+        let mut transcript = Transcript::new(b"test");
+        transcript.submit_data(b"first", &[1, 2]);
+        transcript.submit_data(b"second", &[3]);
+        assert_eq!(challenge, transcript.compute_challenge_in_field::<Fr>(b"challenge"));
+
+        assert_eq!(recording.events.len(), 4);
+        assert_eq!(
+            recording.events[3],
+            TranscriptEvent::Squeeze {
+                label: b"challenge".to_vec(),
+                output: challenge.into_bigint().to_bytes_be()
+            }
+        );
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        let (_, recording_1) = run(&[3]);
+        let (_, recording_2) = run(&[4]);
+
+        assert_eq!(recording_1.first_divergence(&recording_1), None);
+
+        let divergence = recording_1.first_divergence(&recording_2).unwrap();
+        assert_eq!(divergence.index, 2);
+        assert_eq!(
+            divergence.left,
+            Some(TranscriptEvent::Absorb {
+                label: b"second".to_vec(),
+                data: vec![3]
+            })
+        );
+        assert!(divergence.to_string().contains("absorb \"second\": 0x04"));
+    }
+
+    #[test]
+    fn test_divergence_on_shorter_recording() {
+        let (_, recording) = run(&[3]);
+        let mut truncated = recording.clone();
+        truncated.events.pop();
+
+        let divergence = truncated.first_divergence(&recording).unwrap();
+        assert_eq!(divergence.index, 3);
+        assert_eq!(divergence.left, None);
+        assert!(divergence.to_string().contains("end of recording"));
+    }
+
+    #[test]
+    fn test_serialization_round_trip() {
+        let (_, recording) = run(&[3]);
+        let bytes = recording.to_bytes();
+
+        assert_eq!(Recording::from_bytes(&bytes), Some(recording));
+        assert_eq!(Recording::from_bytes(&bytes[..bytes.len() - 1]), None);
+        assert_eq!(Recording::from_bytes(&[2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]), None);
+        assert_eq!(Recording::from_bytes(&[]), Some(Recording::new()));
+    }
+}
@@ -1,37 +1,321 @@
-use sha3::{Keccak256, Digest};
+use digest::Digest;
+use sha2::Sha256;
+use sha3::{Keccak256, Sha3_256};
+use blake2::Blake2b512;
 use ark_ff::PrimeField;
+use crate::transcript_interface::TranscriptInterface;
 
-// Define data structure
-#[derive(Debug, Clone, Default)]
-pub struct Transcript {
-    hasher: Keccak256,
+// Tags telling absorbed messages and squeezed challenges apart
+const ABSORB_OP: u8 = 0;
+const SQUEEZE_OP: u8 = 1;
+const FORK_OP: u8 = 2;
+
+// Extra bytes squeezed for field challenges, bounding the bias of the reduction by 2^-128
+const SECURITY_MARGIN_BYTES: usize = 16;
+
+// Define data structure, generic over the hash function
+#[derive(Debug, Clone)]
+pub struct HashTranscript<H: Digest> {
+    hasher: H,
 }
 
-impl Transcript {
-    // Constructor for creating a new Transcript instance
-    pub fn new() -> Self {
-        Self {
-            hasher: Keccak256::new(),
-        }
+// The default transcript, hashing with Keccak256 as the EVM does
+pub type Transcript = HashTranscript<Keccak256>;
+pub type Sha256Transcript = HashTranscript<Sha256>;
+pub type Sha3Transcript = HashTranscript<Sha3_256>;
+pub type Blake2Transcript = HashTranscript<Blake2b512>;
+pub type Blake3Transcript = HashTranscript<blake3::Hasher>;
+
+impl<H: Digest> HashTranscript<H> {
+    // Constructor for creating a new Transcript instance bound to the protocol name
+    pub fn new(protocol_label: &'static [u8]) -> Self {
+        let mut transcript = Self {
+            hasher: H::new(),
+        };
+        transcript.absorb_frame(ABSORB_OP, b"dom-sep", protocol_label);
+        transcript
     }
 
-    // Method to append new data to the hasher
-    pub fn append(&mut self, new_data: &[u8]) {
-        self.hasher.update(new_data);
+    // Method to append labeled data to the hasher
+    pub fn append(&mut self, label: &'static [u8], new_data: &[u8]) {
+        self.absorb_frame(ABSORB_OP, label, new_data);
     }
 
-    // Method to sample a challenge from the hasher
-    pub fn sample_challenges(&mut self) -> [u8; 32] {
+    // Method to sample a labeled challenge from the hasher
+    pub fn sample_challenges(&mut self, label: &'static [u8]) -> [u8; 32] {
         let mut result = [0_u8; 32];
-        let update_data = self.hasher.finalize_reset();
-        result.copy_from_slice(&update_data[..32]);
-        self.hasher.update(&update_data);
+        result.copy_from_slice(&self.squeeze(label, 32));
         result
     }
 
-    pub fn sample_challenge<F: PrimeField>(&mut self) -> F {
-        let update_data = self.hasher.finalize_reset();
-        F::from_random_bytes(&update_data).expect("Failed to convert bytes to field element")
+    // Samples a field element from enough bytes that the reduction modulo p is
+    // statistically close to uniform, whatever the size of the field
+    pub fn sample_challenge<F: PrimeField>(&mut self, label: &'static [u8]) -> F {
+        let num_bytes = (F::MODULUS_BIT_SIZE as usize).div_ceil(8) + SECURITY_MARGIN_BYTES;
+        F::from_be_bytes_mod_order(&self.squeeze(label, num_bytes))
+    }
+
+    // Samples `n` field elements out of a single squeeze
+    pub fn sample_field_challenges<F: PrimeField>(&mut self, label: &'static [u8], n: usize) -> Vec<F> {
+        let num_bytes = (F::MODULUS_BIT_SIZE as usize).div_ceil(8) + SECURITY_MARGIN_BYTES;
+        self.squeeze(label, n * num_bytes)
+            .chunks(num_bytes)
+            .map(F::from_be_bytes_mod_order)
+            .collect()
+    }
+
+    // Copies the state and binds the copy to the label and index of the child
+    pub fn fork(&self, label: &'static [u8], index: u64) -> Self
+    where
+        H: Clone,
+    {
+        let mut child = self.clone();
+        child.absorb_frame(FORK_OP, label, &index.to_le_bytes());
+        child
+    }
+
+    // Squeezes `num_bytes` bytes out of the current state. The state digest is absorbed
+    // back so later challenges depend on this one, and the output is expanded from it
+    // as H(digest || counter) blocks
+    fn squeeze(&mut self, label: &'static [u8], num_bytes: usize) -> Vec<u8> {
+        self.absorb_frame(SQUEEZE_OP, label, &(num_bytes as u64).to_le_bytes());
+
+        let digest = std::mem::replace(&mut self.hasher, H::new()).finalize();
+        self.hasher.update(&digest);
+
+        let mut output = Vec::with_capacity(num_bytes);
+        let mut counter = 0u64;
+        while output.len() < num_bytes {
+            let block = H::new()
+                .chain_update(&digest)
+                .chain_update(counter.to_le_bytes())
+                .finalize();
+            output.extend_from_slice(&block);
+            counter += 1;
+        }
+
+        output.truncate(num_bytes);
+        output
+    }
+
+    // Every operation is framed as op || len(label) || label || len(data) || data,
+    // so that no two different sequences of operations hash the same bytes
+    fn absorb_frame(&mut self, op: u8, label: &[u8], data: &[u8]) {
+        self.hasher.update([op]);
+        self.hasher.update((label.len() as u64).to_le_bytes());
+        self.hasher.update(label);
+        self.hasher.update((data.len() as u64).to_le_bytes());
+        self.hasher.update(data);
+    }
+
+}
+
+impl<H: Digest + Clone> TranscriptInterface for HashTranscript<H> {
+    fn new(protocol_label: &'static [u8]) -> Self {
+        HashTranscript::new(protocol_label)
+    }
+
+    fn submit_data(&mut self, label: &'static [u8], input_data: &[u8]) {
+        self.append(label, input_data);
     }
 
-}
\ No newline at end of file
+    fn generate_challenge(&mut self, label: &'static [u8]) -> [u8; 32] {
+        self.sample_challenges(label)
+    }
+
+    fn compute_challenge_in_field<F: PrimeField>(&mut self, label: &'static [u8]) -> F {
+        self.sample_challenge(label)
+    }
+
+    fn fork(&self, label: &'static [u8], index: u64) -> Self {
+        HashTranscript::fork(self, label, index)
+    }
+
+    fn compute_challenges_in_field<F: PrimeField>(&mut self, label: &'static [u8], n: usize) -> Vec<F> {
+        self.sample_field_challenges(label, n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::BigInteger;
+    use ark_test_curves::bls12_381::{Fq, Fr};
+
+    fn challenges<T: TranscriptInterface>(protocol_label: &'static [u8], data: &[u8]) -> (Fr, Fr) {
+        let mut transcript = T::new(protocol_label);
+        transcript.submit_data(b"data", data);
+        (
+            transcript.compute_challenge_in_field(b"first"),
+            transcript.compute_challenge_in_field(b"second"),
+        )
+    }
+
+    #[test]
+    fn test_challenges_are_deterministic() {
+        assert_eq!(challenges::<Transcript>(b"test", b"data"), challenges::<Transcript>(b"test", b"data"));
+        assert_ne!(challenges::<Transcript>(b"test", b"data"), challenges::<Transcript>(b"test", b"other data"));
+    }
+
+    #[test]
+    fn test_consecutive_challenges_differ() {
+        let (first, second) = challenges::<Transcript>(b"test", b"data");
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_protocol_label_separates_domains() {
+        assert_ne!(challenges::<Transcript>(b"test", b"data"), challenges::<Transcript>(b"other test", b"data"));
+    }
+
+    #[test]
+    fn test_labels_separate_messages() {
+        let mut transcript_1 = Transcript::new(b"test");
+        transcript_1.append(b"first", b"data");
+
+        let mut transcript_2 = Transcript::new(b"test");
+        transcript_2.append(b"second", b"data");
+
+        assert_ne!(transcript_1.sample_challenges(b"challenge"), transcript_2.sample_challenges(b"challenge"));
+    }
+
+    #[test]
+    fn test_challenge_labels_separate_challenges() {
+        let mut transcript_1 = Transcript::new(b"test");
+        let mut transcript_2 = Transcript::new(b"test");
+
+        assert_ne!(transcript_1.sample_challenges(b"first"), transcript_2.sample_challenges(b"second"));
+    }
+
+    #[test]
+    fn test_message_boundaries_are_unambiguous() {
+        let mut transcript_1 = Transcript::new(b"test");
+        transcript_1.append(b"data", b"ab");
+        transcript_1.append(b"data", b"c");
+
+        let mut transcript_2 = Transcript::new(b"test");
+        transcript_2.append(b"data", b"a");
+        transcript_2.append(b"data", b"bc");
+
+        assert_ne!(transcript_1.sample_challenges(b"challenge"), transcript_2.sample_challenges(b"challenge"));
+    }
+
+    #[test]
+    fn test_sample_challenge_is_chained() {
+        let mut transcript = Transcript::new(b"test");
+        let first: Fr = transcript.sample_challenge(b"challenge");
+        let second: Fr = transcript.sample_challenge(b"challenge");
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_sample_challenge_matches_squeezed_bytes() {
+        let mut transcript_1 = Transcript::new(b"test");
+        let mut transcript_2 = Transcript::new(b"test");
+
+        // 32 bytes for the 255-bit modulus plus the security margin
+        let bytes = transcript_2.squeeze(b"challenge", 48);
+        let challenge: Fr = transcript_1.sample_challenge(b"challenge");
+
+        assert_eq!(challenge, Fr::from_be_bytes_mod_order(&bytes));
+        assert_eq!(transcript_1.sample_challenges(b"next"), transcript_2.sample_challenges(b"next"));
+    }
+
+    #[test]
+    fn test_sample_challenge_in_wide_field() {
+        let mut transcript = Transcript::new(b"test");
+
+        let challenges: Vec<Fq> = (0..256).map(|_| transcript.sample_challenge(b"challenge")).collect();
+
+        // Fq is 381 bits wide, so the challenges have to use more than 256 bits
+        assert!(challenges.iter().any(|c| c.into_bigint().num_bits() > 256));
+    }
+
+    #[test]
+    fn test_squeeze_expands_output() {
+        let mut transcript = Transcript::new(b"test");
+        let bytes = transcript.squeeze(b"challenge", 100);
+
+        assert_eq!(bytes.len(), 100);
+        assert_ne!(bytes[..32], bytes[32..64]);
+    }
+
+    #[test]
+    fn test_hash_backends() {
+        let keccak = challenges::<Transcript>(b"test", b"data");
+        let sha256 = challenges::<Sha256Transcript>(b"test", b"data");
+        let sha3 = challenges::<Sha3Transcript>(b"test", b"data");
+        let blake2 = challenges::<Blake2Transcript>(b"test", b"data");
+        let blake3 = challenges::<Blake3Transcript>(b"test", b"data");
+
+        assert_eq!(sha256, challenges::<Sha256Transcript>(b"test", b"data"));
+        assert_eq!(blake3, challenges::<Blake3Transcript>(b"test", b"data"));
+
+        let all = [keccak, sha256, sha3, blake2, blake3];
+        for i in 0..all.len() {
+            for j in i + 1..all.len() {
+                assert_ne!(all[i], all[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_digest_backend() {
+        // Blake2b512 outputs 64 bytes per block, more than a single challenge needs
+        let mut transcript = Blake2Transcript::new(b"test");
+        let bytes = transcript.squeeze(b"challenge", 100);
+
+        assert_eq!(bytes.len(), 100);
+        assert_ne!(transcript.sample_challenges(b"first"), transcript.sample_challenges(b"second"));
+    }
+
+    #[test]
+    fn test_batch_challenges() {
+        let mut transcript = Transcript::new(b"test");
+        let batch: Vec<Fr> = transcript.compute_challenges_in_field(b"challenges", 4);
+
+        assert_eq!(batch.len(), 4);
+        assert_eq!(batch, Transcript::new(b"test").sample_field_challenges::<Fr>(b"challenges", 4));
+        for i in 0..batch.len() {
+            for j in i + 1..batch.len() {
+                assert_ne!(batch[i], batch[j]);
+            }
+        }
+
+        // The number of challenges is bound, so a shorter batch is not a prefix of a longer one
+        let shorter: Vec<Fr> = Transcript::new(b"test").sample_field_challenges(b"challenges", 3);
+        assert_ne!(shorter[..], batch[..3]);
+
+        // Both transcripts squeezed the same bytes so far
+        let mut other = Transcript::new(b"test");
+        other.sample_field_challenges::<Fr>(b"challenges", 4);
+        assert_eq!(transcript.sample_challenges(b"next"), other.sample_challenges(b"next"));
+    }
+
+    #[test]
+    fn test_fork() {
+        let mut parent = Transcript::new(b"test");
+        parent.append(b"data", b"shared");
+
+        let mut child_0 = parent.fork(b"child", 0);
+        let mut child_1 = parent.fork(b"child", 1);
+        let mut other_child = parent.fork(b"other", 0);
+        let challenge_0 = child_0.sample_challenges(b"challenge");
+
+        assert_eq!(challenge_0, parent.fork(b"child", 0).sample_challenges(b"challenge"));
+        assert_ne!(challenge_0, child_1.sample_challenges(b"challenge"));
+        assert_ne!(challenge_0, other_child.sample_challenges(b"challenge"));
+        assert_ne!(challenge_0, parent.clone().sample_challenges(b"challenge"));
+
+        // Children are bound to what the parent absorbed before forking
+        let mut other_parent = Transcript::new(b"test");
+        other_parent.append(b"data", b"other");
+        assert_ne!(challenge_0, other_parent.fork(b"child", 0).sample_challenges(b"challenge"));
+
+        // Forking leaves the parent untouched
+        let mut unforked = Transcript::new(b"test");
+        unforked.append(b"data", b"shared");
+        assert_eq!(parent.sample_challenges(b"challenge"), unforked.sample_challenges(b"challenge"));
+    }
+}
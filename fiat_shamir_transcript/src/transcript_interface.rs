@@ -1,8 +1,28 @@
 use ark_ff::PrimeField;
+use crate::absorb::Absorb;
 
+/// A Fiat-Shamir transcript where every message and challenge carries a label,
+/// and the transcript itself is bound to a protocol name at construction.
 pub trait TranscriptInterface {
-    fn new() -> Self;
-    fn submit_data(&mut self, input_data: &[u8]);
-    fn generate_challenge(&mut self) -> [u8; 32];
-    fn compute_challenge_in_field<F: PrimeField>(&mut self) -> F;
-}
\ No newline at end of file
+    fn new(protocol_label: &'static [u8]) -> Self;
+    fn submit_data(&mut self, label: &'static [u8], input_data: &[u8]);
+    fn generate_challenge(&mut self, label: &'static [u8]) -> [u8; 32];
+    fn compute_challenge_in_field<F: PrimeField>(&mut self, label: &'static [u8]) -> F;
+
+    /// Derives the child transcript number `index` under `label`. The child starts from
+    /// the current state, so it is bound to everything absorbed so far, but the parent
+    /// and the children with other labels or indices evolve independently from it.
+    fn fork(&self, label: &'static [u8], index: u64) -> Self
+    where
+        Self: Sized;
+
+    /// Submits `value` under its canonical encoding.
+    fn absorb<A: Absorb + ?Sized>(&mut self, label: &'static [u8], value: &A) {
+        self.submit_data(label, &value.to_transcript_bytes());
+    }
+
+    /// Samples `n` field challenges under a single label.
+    fn compute_challenges_in_field<F: PrimeField>(&mut self, label: &'static [u8], n: usize) -> Vec<F> {
+        (0..n).map(|_| self.compute_challenge_in_field(label)).collect()
+    }
+}
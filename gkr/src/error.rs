@@ -0,0 +1,55 @@
+use std::fmt;
+
+use ark_ff::PrimeField;
+use circuit::error::CircuitError;
+use sumcheck::error::SumcheckError;
+
+/// Why the GKR verifier rejected a proof. Layers are counted from the output layer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GkrError<F: PrimeField> {
+    /// The proof does not have one sumcheck and one pair of evaluations per layer.
+    WrongNumberOfLayers { expected: usize, actual: usize },
+
+    /// The proof does not claim one value per output gate.
+    WrongNumberOfOutputs { expected: usize, actual: usize },
+
+    /// The sumcheck of a layer failed.
+    Sumcheck { layer: usize, error: SumcheckError<F> },
+
+    /// The sumcheck of a layer does not end on the value given by the wiring
+    /// predicates and the evaluations of the layer below.
+    LayerEvaluationMismatch { layer: usize, expected: F, actual: F },
+
+    /// The evaluations of the input layer are not those of the given input.
+    InputEvaluationMismatch,
+
+    /// The circuit or the input given to the verifier is malformed.
+    Circuit(CircuitError),
+}
+
+impl<F: PrimeField> fmt::Display for GkrError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongNumberOfLayers { expected, actual } => {
+                write!(f, "the proof covers {} layers instead of {}", actual, expected)
+            }
+            Self::WrongNumberOfOutputs { expected, actual } => {
+                write!(f, "expected {} outputs, got {}", expected, actual)
+            }
+            Self::Sumcheck { layer, error } => write!(f, "sumcheck failed at layer {}: {}", layer, error),
+            Self::LayerEvaluationMismatch { layer, expected, actual } => {
+                write!(f, "layer polynomial evaluation mismatch at layer {}: {} != {}", layer, actual, expected)
+            }
+            Self::InputEvaluationMismatch => write!(f, "input layer evaluation mismatch"),
+            Self::Circuit(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl<F: PrimeField> std::error::Error for GkrError<F> {}
+
+impl<F: PrimeField> From<CircuitError> for GkrError<F> {
+    fn from(error: CircuitError) -> Self {
+        Self::Circuit(error)
+    }
+}
@@ -0,0 +1,4 @@
+pub mod utils;
+pub mod error;
+pub mod prover;
+pub mod verifier;
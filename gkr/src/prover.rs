@@ -0,0 +1,115 @@
+use ark_ff::PrimeField;
+use circuit::utils::*;
+use fiat_shamir_transcript::absorb::{write_length, Absorb};
+use fiat_shamir_transcript::transcript_interface::TranscriptInterface;
+use sumcheck::multi_composed::multi_composed_sumcheck::{MultiComposedSumcheck, MultiComposedSumcheckProof};
+use crate::utils::*;
+
+// GKRProof structure
+#[derive(Debug, Clone, PartialEq)]
+pub struct GKRProof<F: PrimeField> {
+    // The claimed values of the output layer
+    pub outputs: Vec<F>,
+    // One sumcheck proof per layer, from the output layer down
+    pub sumcheck_proofs: Vec<MultiComposedSumcheckProof<F>>,
+    // W_{i+1}(r_b) and W_{i+1}(r_c) at the end of every layer sumcheck
+    pub w_evaluations: Vec<(F, F)>,
+}
+
+impl<F: PrimeField> Absorb for GKRProof<F> {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        self.outputs.as_slice().write_transcript_bytes(bytes);
+        self.sumcheck_proofs.as_slice().write_transcript_bytes(bytes);
+        write_length(self.w_evaluations.len(), bytes);
+        for (w_b, w_c) in &self.w_evaluations {
+            w_b.write_transcript_bytes(bytes);
+            w_c.write_transcript_bytes(bytes);
+        }
+    }
+}
+
+// Prover structure and implementation
+#[derive(Clone)]
+pub struct Prover {
+    circuit: Circuit,
+}
+
+impl Prover {
+    pub fn new(circuit: Circuit) -> Self {
+        Self { circuit }
+    }
+
+    /// Proves `evaluation` on a fresh `transcript`, which the verifier has to start from the same state.
+    pub fn prove<F: PrimeField, T: TranscriptInterface>(&self, evaluation: &CircuitEvaluation<F>, transcript: &mut T) -> GKRProof<F> {
+        let mut sumcheck_proofs = Vec::with_capacity(self.circuit.layers.len());
+        let mut w_evaluations = Vec::with_capacity(self.circuit.layers.len());
+
+        // The statement: the circuit, its input and the claimed outputs
+        let input = evaluation.layers.last().expect("an evaluation has at least the input layer");
+        transcript.absorb(b"gkr_circuit", &self.circuit);
+        transcript.absorb(b"gkr_input", input.as_slice());
+        let outputs = evaluation.layers[0].clone();
+        transcript.absorb(b"gkr_outputs", outputs.as_slice());
+
+        let output_vars = self.circuit.num_vars_at(0).expect("circuit has no layers");
+        let r_0: Vec<F> = transcript.compute_challenges_in_field(b"gkr_output_challenge", output_vars);
+        let mut claim = evaluation.layer_mle(0).poly.eval_full(&r_0);
+        let mut claims = vec![(F::one(), r_0)];
+
+        for layer in 0..self.circuit.layers.len() {
+            // The points are built to the sizes of the layers, so only a malformed circuit fails
+            let add = combine_wiring_mle(&self.circuit.add_i_mle(layer), &claims).expect("malformed circuit");
+            let mul = combine_wiring_mle(&self.circuit.mul_i_mle(layer), &claims).expect("malformed circuit");
+            let w = evaluation.layer_mle(layer + 1).poly;
+
+            let sumcheck = MultiComposedSumcheck::new_with_sum(layer_sumcheck_polys(add, mul, &w), claim);
+            let (sumcheck_proof, challenges) = sumcheck.prove(transcript);
+            sumcheck_proofs.push(sumcheck_proof);
+
+            let (r_b, r_c) = challenges.split_at(w.variables);
+            let w_b = w.eval_full(r_b);
+            let w_c = w.eval_full(r_c);
+            transcript.absorb(b"gkr_layer_evaluations", [w_b, w_c].as_slice());
+            w_evaluations.push((w_b, w_c));
+
+            let alpha = transcript.compute_challenge_in_field(b"gkr_alpha");
+            let beta = transcript.compute_challenge_in_field(b"gkr_beta");
+            claim = alpha * w_b + beta * w_c;
+            claims = vec![(alpha, r_b.to_vec()), (beta, r_c.to_vec())];
+        }
+
+        GKRProof {
+            outputs,
+            sumcheck_proofs,
+            w_evaluations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fiat_shamir_transcript::transcript::Transcript;
+    use ark_test_curves::bls12_381::Fr;
+
+    #[test]
+    fn test_proof_shape() {
+        let circuit = Circuit::new(
+            vec![
+                CircuitLayer::new(vec![Gate::new(GateType::Mul, [0, 1])]),
+                CircuitLayer::new(vec![Gate::new(GateType::Add, [0, 1]), Gate::new(GateType::Mul, [2, 3])]),
+            ],
+            4,
+        )
+        .unwrap();
+        let evaluation = circuit.evaluate(&[Fr::from(1), Fr::from(2), Fr::from(3), Fr::from(4)]).unwrap();
+
+        let proof = Prover::new(circuit).prove(&evaluation, &mut Transcript::new(b"gkr"));
+
+        assert_eq!(proof.outputs, vec![Fr::from(36)]);
+        assert_eq!(proof.sumcheck_proofs.len(), 2);
+        assert_eq!(proof.sumcheck_proofs[0].round_polys.len(), 2);
+        assert_eq!(proof.sumcheck_proofs[1].round_polys.len(), 4);
+        assert_eq!(proof.w_evaluations.len(), 2);
+    }
+}
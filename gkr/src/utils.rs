@@ -0,0 +1,120 @@
+use ark_ff::PrimeField;
+use circuit::error::CircuitError;
+use circuit::utils::*;
+use polynomials::composed::composed_multilinear::ComposedMultilinearPolynomial;
+use polynomials::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
+
+/// Fixes the `a` block of a wiring predicate at each of the given points and returns
+/// the weighted sum $\sum_j \lambda_j \cdot \text{wiring}(r_j, b, c)$.
+pub fn combine_wiring_mle<F: PrimeField>(
+    wiring: &WiringPredicate,
+    claims: &[(F, Vec<F>)],
+) -> Result<MultiLinearPolynomial<F>, CircuitError> {
+    let mut combined = MultiLinearPolynomial::zero(2 * wiring.bc_vars);
+
+    for (coefficient, point) in claims {
+        let mut fixed = wiring.fix_a(point)?;
+        for eval in fixed.evaluations.iter_mut() {
+            *eval *= coefficient;
+        }
+        combined += fixed;
+    }
+
+    Ok(combined)
+}
+
+/// Evaluates the weighted sum returned by `combine_wiring_mle` at the point $(b, c)$.
+pub fn evaluate_combined_wiring<F: PrimeField>(
+    wiring: &WiringPredicate,
+    claims: &[(F, Vec<F>)],
+    bc_point: &[F],
+) -> Result<F, CircuitError> {
+    if bc_point.len() != 2 * wiring.bc_vars {
+        return Err(CircuitError::WrongNumberOfVariables {
+            expected: 2 * wiring.bc_vars,
+            actual: bc_point.len(),
+        });
+    }
+    let (r_b, r_c) = bc_point.split_at(wiring.bc_vars);
+
+    claims
+        .iter()
+        .map(|(coefficient, point)| Ok(*coefficient * wiring.evaluate(point, r_b, r_c)?))
+        .sum()
+}
+
+/// Lifts $W(x)$ to the $(b, c)$ variables as $W(b)$.
+pub fn extend_to_b<F: PrimeField>(w: &MultiLinearPolynomial<F>) -> MultiLinearPolynomial<F> {
+    let evaluations = w
+        .evaluations
+        .iter()
+        .flat_map(|&eval| std::iter::repeat_n(eval, w.evaluations.len()))
+        .collect();
+
+    MultiLinearPolynomial::new(2 * w.variables, evaluations)
+}
+
+/// Lifts $W(x)$ to the $(b, c)$ variables as $W(c)$.
+pub fn extend_to_c<F: PrimeField>(w: &MultiLinearPolynomial<F>) -> MultiLinearPolynomial<F> {
+    let evaluations = (0..w.evaluations.len())
+        .flat_map(|_| w.evaluations.iter().cloned())
+        .collect();
+
+    MultiLinearPolynomial::new(2 * w.variables, evaluations)
+}
+
+/// The terms of the layer polynomial summed over $(b, c)$ in the GKR layer sumcheck:
+/// $\text{add}(b, c) (W(b) + W(c)) + \text{mul}(b, c) W(b) W(c)$.
+pub fn layer_sumcheck_polys<F: PrimeField>(
+    add: MultiLinearPolynomial<F>,
+    mul: MultiLinearPolynomial<F>,
+    w: &MultiLinearPolynomial<F>,
+) -> Vec<ComposedMultilinearPolynomial<F>> {
+    let w_b = extend_to_b(w);
+    let w_c = extend_to_c(w);
+
+    vec![
+        ComposedMultilinearPolynomial::new(vec![add.clone(), w_b.clone()]),
+        ComposedMultilinearPolynomial::new(vec![add, w_c.clone()]),
+        ComposedMultilinearPolynomial::new(vec![mul, w_b, w_c]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr;
+
+    type F = Fr;
+
+    fn create_test_circuit() -> Circuit {
+        Circuit::new(
+            vec![
+                CircuitLayer::new(vec![Gate::new(GateType::Add, [0, 1]), Gate::new(GateType::Mul, [1, 0])]),
+                CircuitLayer::new(vec![Gate::new(GateType::Mul, [0, 1]), Gate::new(GateType::Add, [2, 3])]),
+            ],
+            4,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_extend_to_b_and_c() {
+        let w = MultiLinearPolynomial::new(1, vec![F::from(3), F::from(5)]);
+
+        assert_eq!(extend_to_b(&w).evaluations, vec![F::from(3), F::from(3), F::from(5), F::from(5)]);
+        assert_eq!(extend_to_c(&w).evaluations, vec![F::from(3), F::from(5), F::from(3), F::from(5)]);
+    }
+
+    #[test]
+    fn test_combine_wiring_mle() {
+        let circuit = create_test_circuit();
+        let add = circuit.add_i_mle(1);
+        let claims = vec![(F::from(2), vec![F::from(7)]), (F::from(3), vec![F::from(11)])];
+
+        let combined = combine_wiring_mle(&add, &claims).unwrap();
+        let bc_point = vec![F::from(4), F::from(9), F::from(13), F::from(6)];
+
+        assert_eq!(combined.eval_full(&bc_point), evaluate_combined_wiring(&add, &claims, &bc_point).unwrap());
+    }
+}
@@ -0,0 +1,318 @@
+use ark_ff::PrimeField;
+use circuit::error::CircuitError;
+use circuit::utils::*;
+use fiat_shamir_transcript::transcript_interface::TranscriptInterface;
+use sumcheck::multi_composed::multi_composed_sumcheck::MultiComposedSumcheck;
+use crate::error::GkrError;
+use crate::prover::*;
+use crate::utils::*;
+
+#[derive(Clone)]
+pub struct Verifier {
+    circuit: Circuit,
+}
+
+impl Verifier {
+    pub fn new(circuit: Circuit) -> Self {
+        Self { circuit }
+    }
+
+    /// Checks that `proof.outputs` is the output of the circuit on `input`, starting from
+    /// a `transcript` in the same state as the prover's.
+    pub fn verify<F: PrimeField, T: TranscriptInterface>(
+        &self,
+        input: &[F],
+        proof: &GKRProof<F>,
+        transcript: &mut T,
+    ) -> Result<(), GkrError<F>> {
+        // The fields of a circuit are public, so it may not have been built by `Circuit::new`
+        self.circuit.validate()?;
+        let num_layers = self.circuit.layers.len();
+
+        for actual in [proof.sumcheck_proofs.len(), proof.w_evaluations.len()] {
+            if actual != num_layers {
+                return Err(GkrError::WrongNumberOfLayers {
+                    expected: num_layers,
+                    actual,
+                });
+            }
+        }
+
+        let output_vars = self.circuit.num_vars_at(0).expect("the circuit has layers");
+        if proof.outputs.len() != self.circuit.num_outputs() {
+            return Err(GkrError::WrongNumberOfOutputs {
+                expected: self.circuit.num_outputs(),
+                actual: proof.outputs.len(),
+            });
+        }
+
+        if input.len() != self.circuit.num_inputs() {
+            return Err(CircuitError::WrongNumberOfInputs {
+                expected: self.circuit.num_inputs(),
+                actual: input.len(),
+            }
+            .into());
+        }
+
+        // The same statement as the prover: the circuit, the input and the claimed outputs
+        transcript.absorb(b"gkr_circuit", &self.circuit);
+        transcript.absorb(b"gkr_input", input);
+        transcript.absorb(b"gkr_outputs", proof.outputs.as_slice());
+
+        let r_0: Vec<F> = transcript.compute_challenges_in_field(b"gkr_output_challenge", output_vars);
+        let mut claim = LayerPolynomial::new(&proof.outputs).poly.eval_full(&r_0);
+        let mut claims = vec![(F::one(), r_0)];
+
+        for layer in 0..num_layers {
+            let bc_vars = self.circuit.num_vars_at(layer + 1).expect("the input layer is below the last layer");
+
+            let sub_claim = MultiComposedSumcheck::verify(
+                &proof.sumcheck_proofs[layer],
+                claim,
+                2 * bc_vars,
+                3,
+                transcript,
+            )
+            .map_err(|error| GkrError::Sumcheck { layer, error })?;
+
+            let (w_b, w_c) = proof.w_evaluations[layer];
+            let add = evaluate_combined_wiring(
+                &self.circuit.add_i_mle(layer),
+                &claims,
+                &sub_claim.challenges,
+            )?;
+            let mul = evaluate_combined_wiring(
+                &self.circuit.mul_i_mle(layer),
+                &claims,
+                &sub_claim.challenges,
+            )?;
+
+            let expected = add * (w_b + w_c) + mul * w_b * w_c;
+            if expected != sub_claim.claimed_eval {
+                return Err(GkrError::LayerEvaluationMismatch {
+                    layer,
+                    expected,
+                    actual: sub_claim.claimed_eval,
+                });
+            }
+
+            transcript.absorb(b"gkr_layer_evaluations", [w_b, w_c].as_slice());
+
+            let alpha = transcript.compute_challenge_in_field(b"gkr_alpha");
+            let beta = transcript.compute_challenge_in_field(b"gkr_beta");
+            claim = alpha * w_b + beta * w_c;
+
+            let (r_b, r_c) = sub_claim.challenges.split_at(bc_vars);
+            claims = vec![(alpha, r_b.to_vec()), (beta, r_c.to_vec())];
+        }
+
+        let input_poly = LayerPolynomial::new(input).poly;
+        let (w_b, w_c) = proof.w_evaluations[num_layers - 1];
+        if input_poly.eval_full(&claims[0].1) != w_b || input_poly.eval_full(&claims[1].1) != w_c {
+            return Err(GkrError::InputEvaluationMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fiat_shamir_transcript::transcript::Transcript;
+    use ark_test_curves::bls12_381::Fr;
+
+    type F = Fr;
+
+    fn create_test_circuit() -> Circuit {
+        Circuit::new(
+            vec![
+                CircuitLayer::new(vec![Gate::new(GateType::Add, [0, 1]), Gate::new(GateType::Mul, [2, 3])]),
+                CircuitLayer::new(vec![
+                    Gate::new(GateType::Add, [0, 1]),
+                    Gate::new(GateType::Mul, [2, 3]),
+                    Gate::new(GateType::Mul, [4, 5]),
+                    Gate::new(GateType::Mul, [6, 7]),
+                ]),
+            ],
+            8,
+        )
+        .unwrap()
+    }
+
+    fn create_test_input() -> Vec<F> {
+        (1..=8).map(F::from).collect()
+    }
+
+    #[test]
+    fn test_gkr_proof() {
+        let circuit = create_test_circuit();
+        let evaluation = circuit.evaluate(&create_test_input()).unwrap();
+
+        let proof = Prover::new(circuit.clone()).prove(&evaluation, &mut Transcript::new(b"gkr"));
+
+        assert_eq!(proof.outputs, vec![F::from(15), F::from(1680)]);
+        assert!(Verifier::new(circuit).verify(&create_test_input(), &proof, &mut Transcript::new(b"gkr")).is_ok());
+    }
+
+    #[test]
+    fn test_prover_and_verifier_are_reusable() {
+        let circuit = create_test_circuit();
+        let evaluation = circuit.evaluate(&create_test_input()).unwrap();
+        let prover = Prover::new(circuit.clone());
+        let verifier = Verifier::new(circuit);
+
+        // Every run starts from its own transcript, so nothing leaks from the previous one
+        let proof = prover.prove(&evaluation, &mut Transcript::new(b"gkr"));
+        assert_eq!(prover.prove(&evaluation, &mut Transcript::new(b"gkr")), proof);
+        for _ in 0..2 {
+            assert!(verifier.verify(&create_test_input(), &proof, &mut Transcript::new(b"gkr")).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_gkr_proof_single_output() {
+        let circuit = Circuit::new(
+            vec![
+                CircuitLayer::new(vec![Gate::new(GateType::Mul, [0, 1])]),
+                CircuitLayer::new(vec![Gate::new(GateType::Add, [0, 1]), Gate::new(GateType::Mul, [2, 3])]),
+            ],
+            4,
+        )
+        .unwrap();
+        let input = vec![F::from(1), F::from(2), F::from(3), F::from(4)];
+        let evaluation = circuit.evaluate(&input).unwrap();
+
+        let proof = Prover::new(circuit.clone()).prove(&evaluation, &mut Transcript::new(b"gkr"));
+
+        assert!(Verifier::new(circuit).verify(&input, &proof, &mut Transcript::new(b"gkr")).is_ok());
+    }
+
+    #[test]
+    fn test_gkr_proof_non_power_of_two_layers() {
+        let circuit = Circuit::new(
+            vec![
+                CircuitLayer::new(vec![
+                    Gate::new(GateType::Add, [0, 1]),
+                    Gate::new(GateType::Mul, [1, 2]),
+                    Gate::new(GateType::Add, [2, 0]),
+                ]),
+                CircuitLayer::new(vec![
+                    Gate::new(GateType::Mul, [0, 1]),
+                    Gate::new(GateType::Add, [2, 3]),
+                    Gate::new(GateType::Mul, [4, 5]),
+                ]),
+            ],
+            6,
+        )
+        .unwrap();
+        let input: Vec<F> = (1..=6).map(F::from).collect();
+        let evaluation = circuit.evaluate(&input).unwrap();
+
+        let proof = Prover::new(circuit.clone()).prove(&evaluation, &mut Transcript::new(b"gkr"));
+
+        assert_eq!(proof.outputs, vec![F::from(9), F::from(210), F::from(32)]);
+        assert!(Verifier::new(circuit).verify(&input, &proof, &mut Transcript::new(b"gkr")).is_ok());
+    }
+
+    #[test]
+    fn test_gkr_proof_wrong_output() {
+        let circuit = create_test_circuit();
+        let evaluation = circuit.evaluate(&create_test_input()).unwrap();
+
+        let mut proof = Prover::new(circuit.clone()).prove(&evaluation, &mut Transcript::new(b"gkr"));
+        proof.outputs[1] = F::from(1681);
+
+        assert!(matches!(
+            Verifier::new(circuit).verify(&create_test_input(), &proof, &mut Transcript::new(b"gkr")),
+            Err(GkrError::Sumcheck { layer: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_gkr_proof_wrong_input() {
+        let circuit = create_test_circuit();
+        let evaluation = circuit.evaluate(&create_test_input()).unwrap();
+
+        let proof = Prover::new(circuit.clone()).prove(&evaluation, &mut Transcript::new(b"gkr"));
+
+        let mut input = create_test_input();
+        input[7] = F::from(9);
+        // The input is part of the transcript, so the challenges already differ
+        assert!(matches!(
+            Verifier::new(circuit.clone()).verify(&input, &proof, &mut Transcript::new(b"gkr")),
+            Err(GkrError::Sumcheck { layer: 0, .. })
+        ));
+
+        input.pop();
+        assert_eq!(
+            Verifier::new(circuit).verify(&input, &proof, &mut Transcript::new(b"gkr")),
+            Err(GkrError::Circuit(CircuitError::WrongNumberOfInputs { expected: 8, actual: 7 }))
+        );
+    }
+
+    #[test]
+    fn test_circuit_without_layers() {
+        let circuit = create_test_circuit();
+        let evaluation = circuit.evaluate(&create_test_input()).unwrap();
+        let proof = Prover::new(circuit).prove(&evaluation, &mut Transcript::new(b"gkr"));
+
+        let empty = Circuit { layers: vec![], num_inputs: 8 };
+        assert_eq!(
+            Verifier::new(empty).verify(&create_test_input(), &proof, &mut Transcript::new(b"gkr")),
+            Err(GkrError::Circuit(CircuitError::NoLayers))
+        );
+    }
+
+    #[test]
+    fn test_malformed_circuit() {
+        let circuit = create_test_circuit();
+        let evaluation = circuit.evaluate(&create_test_input()).unwrap();
+        let proof = Prover::new(circuit.clone()).prove(&evaluation, &mut Transcript::new(b"gkr"));
+
+        // The first gate reads past the end of the layer below it
+        let mut layers = circuit.layers.clone();
+        layers[0].layer[0] = Gate::new(GateType::Add, [0, 9]);
+        let malformed = Circuit { layers, num_inputs: 8 };
+        assert_eq!(
+            Verifier::new(malformed).verify(&create_test_input(), &proof, &mut Transcript::new(b"gkr")),
+            Err(GkrError::Circuit(CircuitError::GateInputOutOfRange { layer: 0, gate: 0, input: 9 }))
+        );
+    }
+
+    #[test]
+    fn test_gkr_proof_wrong_evaluation() {
+        let circuit = create_test_circuit();
+        let mut evaluation = circuit.evaluate(&create_test_input()).unwrap();
+        evaluation.layers[1][2] = F::from(31);
+
+        let proof = Prover::new(circuit.clone()).prove(&evaluation, &mut Transcript::new(b"gkr"));
+
+        assert!(Verifier::new(circuit).verify(&create_test_input(), &proof, &mut Transcript::new(b"gkr")).is_err());
+    }
+
+    #[test]
+    fn test_gkr_proof_bound_to_statement() {
+        let circuit = create_test_circuit();
+        let evaluation = circuit.evaluate(&create_test_input()).unwrap();
+        let proof = Prover::new(circuit.clone()).prove(&evaluation, &mut Transcript::new(b"gkr"));
+
+        // Swapping the inputs of an addition keeps the outputs, but not the challenges
+        let mut input = create_test_input();
+        input.swap(0, 1);
+        assert_eq!(circuit.evaluate(&input).unwrap().layers[0], proof.outputs);
+        assert!(matches!(
+            Verifier::new(circuit.clone()).verify(&input, &proof, &mut Transcript::new(b"gkr")),
+            Err(GkrError::Sumcheck { layer: 0, .. })
+        ));
+
+        let mut layers = circuit.layers.clone();
+        layers[1].layer[0] = Gate::new(GateType::Add, [1, 0]);
+        let rewired = Circuit::new(layers, 8).unwrap();
+        assert_eq!(rewired.evaluate(&create_test_input()).unwrap().layers[0], proof.outputs);
+        assert!(matches!(
+            Verifier::new(rewired).verify(&create_test_input(), &proof, &mut Transcript::new(b"gkr")),
+            Err(GkrError::Sumcheck { layer: 0, .. })
+        ));
+    }
+}
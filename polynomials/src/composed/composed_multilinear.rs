@@ -2,9 +2,11 @@ use std::ops::{Add, AddAssign};
 
 use ark_ff::PrimeField;
 use crate::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
+use crate::error::PolynomialError;
 
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use fiat_shamir_transcript::absorb::Absorb;
 
 #[derive(Clone, PartialEq, Eq, Hash, Default, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ComposedMultilinearPolynomial<F: PrimeField> {
@@ -12,14 +14,23 @@ pub struct ComposedMultilinearPolynomial<F: PrimeField> {
 }
 
 impl<F: PrimeField> ComposedMultilinearPolynomial<F> {
+    /// # Panics
+    ///
+    /// If `polys` is empty or the polynomials differ in their number of variables;
+    /// see [`Self::try_new`].
     pub fn new(polys: Vec<MultiLinearPolynomial<F>>) -> Self {
-        assert!(!polys.is_empty(), "At least one polynomial is required");
-        let variables = polys[0].variables;
-        assert!(
-            polys.iter().all(|p| p.variables == variables),
-            "All polynomials must have the same number of variables"
-        );
-        Self { polys }
+        Self::try_new(polys).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_new(polys: Vec<MultiLinearPolynomial<F>>) -> Result<Self, PolynomialError> {
+        let variables = polys.first().ok_or(PolynomialError::NoPolynomials)?.variables;
+        if let Some(poly) = polys.iter().find(|p| p.variables != variables) {
+            return Err(PolynomialError::VariableMismatch {
+                expected: variables,
+                actual: poly.variables,
+            });
+        }
+        Ok(Self { polys })
     }
 
     pub fn evaluate(&self, point: &[F]) -> F {
@@ -33,10 +44,14 @@ impl<F: PrimeField> ComposedMultilinearPolynomial<F> {
         }
     }
 
-    pub fn partial_evaluations(&self, evaluation_points: Vec<F>, variable_indices: Vec<usize>) -> Self {
-        Self {
-            polys: self.polys.iter().map(|p| p.partial_evaluations(evaluation_points.clone(), variable_indices.clone())).collect(),
-        }
+    pub fn partial_evaluations(&self, evaluation_points: Vec<F>, variable_indices: Vec<usize>) -> Result<Self, PolynomialError> {
+        Ok(Self {
+            polys: self
+                .polys
+                .iter()
+                .map(|p| p.partial_evaluations(evaluation_points.clone(), variable_indices.clone()))
+                .collect::<Result<_, _>>()?,
+        })
     }
 
     pub fn elementwise_product(&self) -> Vec<F> {
@@ -49,14 +64,11 @@ impl<F: PrimeField> ComposedMultilinearPolynomial<F> {
     pub fn max_degree(&self) -> usize {
         self.polys.len()
     }
+}
 
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::new();
-        bytes.extend_from_slice(&(self.polys.len() as u32).to_le_bytes());
-        for poly in &self.polys {
-            bytes.extend_from_slice(&poly.to_bytes());
-        }
-        bytes
+impl<F: PrimeField> Absorb for ComposedMultilinearPolynomial<F> {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        self.polys.as_slice().write_transcript_bytes(bytes);
     }
 }
 
@@ -84,7 +96,6 @@ impl<F: PrimeField> AddAssign for ComposedMultilinearPolynomial<F> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ark_ff::Field;
     use ark_test_curves::bls12_381::Fr;
 
     type F = Fr;
@@ -108,7 +119,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "All polynomials must have the same number of variables")]
+    #[should_panic(expected = "expected a polynomial in 2 variables, got 3")]
     fn test_new_different_variables() {
         let poly1 = create_test_poly();
         let poly2 = MultiLinearPolynomial::new(3, vec![F::from(0); 8]);
@@ -116,6 +127,18 @@ mod tests {
     }
 
     #[test]
+    fn test_try_new() {
+        let poly2 = MultiLinearPolynomial::new(3, vec![F::from(0); 8]);
+
+        assert_eq!(ComposedMultilinearPolynomial::<F>::try_new(vec![]), Err(PolynomialError::NoPolynomials));
+        assert_eq!(
+            ComposedMultilinearPolynomial::try_new(vec![create_test_poly(), poly2]),
+            Err(PolynomialError::VariableMismatch { expected: 2, actual: 3 })
+        );
+    }
+
+    #[test]
+    #[allow(clippy::useless_vec)]
     fn test_evaluation() {
 
         let mle1 = MultiLinearPolynomial::new(2, vec![F::from(0), F::from(1), F::from(2), F::from(3)]);
@@ -0,0 +1,59 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolynomialError {
+    /// A multilinear polynomial was given no evaluations.
+    EmptyEvaluations,
+
+    /// The number of evaluations is not 2 to the power of the number of variables.
+    WrongEvaluationsLength { variables: usize, len: usize },
+
+    /// Polynomials that must share their number of variables do not.
+    VariableMismatch { expected: usize, actual: usize },
+
+    /// A product or a sum of polynomials was given no polynomials.
+    NoPolynomials,
+
+    /// The evaluation points and the variables to fix them at differ in length.
+    PartialEvaluationMismatch { points: usize, indices: usize },
+
+    /// The x and y coordinates of an interpolation differ in length.
+    CoordinatesMismatch { x_len: usize, y_len: usize },
+
+    /// Two interpolation points share the same x coordinate.
+    DuplicateInterpolationPoint,
+
+    /// The field has no subgroup of roots of unity of this size.
+    DomainTooLarge { size: usize, two_adicity: u32 },
+
+    /// The divisor is the zero polynomial.
+    DivisionByZero,
+}
+
+impl fmt::Display for PolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyEvaluations => write!(f, "evaluations cannot be empty"),
+            Self::WrongEvaluationsLength { variables, len } => {
+                write!(f, "{} evaluations given for a polynomial in {} variables", len, variables)
+            }
+            Self::VariableMismatch { expected, actual } => {
+                write!(f, "expected a polynomial in {} variables, got {}", expected, actual)
+            }
+            Self::NoPolynomials => write!(f, "at least one polynomial is required"),
+            Self::PartialEvaluationMismatch { points, indices } => {
+                write!(f, "{} evaluation points given for {} variable indices", points, indices)
+            }
+            Self::CoordinatesMismatch { x_len, y_len } => {
+                write!(f, "{} x coordinates given for {} y coordinates", x_len, y_len)
+            }
+            Self::DuplicateInterpolationPoint => write!(f, "interpolation points must have distinct x coordinates"),
+            Self::DomainTooLarge { size, two_adicity } => {
+                write!(f, "no domain of size {} in a field of two-adicity {}", size, two_adicity)
+            }
+            Self::DivisionByZero => write!(f, "division by the zero polynomial"),
+        }
+    }
+}
+
+impl std::error::Error for PolynomialError {}
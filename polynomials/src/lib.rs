@@ -1,3 +1,5 @@
 pub mod multilinear_pol;
 pub mod univariate_poly;
-pub mod composed;
\ No newline at end of file
+pub mod composed;
+pub mod virtual_poly;
+pub mod error;
@@ -0,0 +1,144 @@
+use ark_ff::PrimeField;
+use super::multilinear_poly::MultiLinearPolynomial;
+
+// The first variable is the most significant bit of the hypercube index, as in
+// `MultiLinearPolynomial::partial_eval`, so eq_poly(r).eval_full(x) == eq_eval(x, r).
+
+/// The multilinear extension of $\text{eq}(x, r) = \prod_i (x_i r_i + (1 - x_i)(1 - r_i))$,
+/// built in $O(2^n)$ field operations.
+pub fn eq_poly<F: PrimeField>(r: &[F]) -> MultiLinearPolynomial<F> {
+    let mut evaluations = vec![F::zero(); 1 << r.len()];
+    evaluations[0] = F::one();
+
+    // After fixing i variables the first 2^i entries hold the table over them;
+    // every entry then splits into its (x_i = 0, x_i = 1) children
+    for (i, &r_i) in r.iter().enumerate() {
+        for j in (0..1 << i).rev() {
+            let parent = evaluations[j];
+            let high = parent * r_i;
+            evaluations[2 * j + 1] = high;
+            evaluations[2 * j] = parent - high;
+        }
+    }
+
+    MultiLinearPolynomial::new(r.len(), evaluations)
+}
+
+/// $\text{eq}(x, r)$ at two arbitrary points, in $O(n)$ field operations.
+pub fn eq_eval<F: PrimeField>(x: &[F], r: &[F]) -> F {
+    assert_eq!(x.len(), r.len(), "Points must have the same number of variables");
+
+    x.iter()
+        .zip(r)
+        .map(|(&x_i, &r_i)| x_i * r_i + (F::one() - x_i) * (F::one() - r_i))
+        .product()
+}
+
+/// Yields the evaluations of `eq_poly(r)` one by one in hypercube order, keeping only
+/// the O(n) prefix products in memory. Each entry costs O(1) multiplications amortized.
+#[derive(Clone, Debug)]
+pub struct EqEvaluations<F: PrimeField> {
+    r: Vec<F>,
+    // prefix[k] is the product of the factors of the first k variables at the current index
+    prefix: Vec<F>,
+    index: usize,
+}
+
+impl<F: PrimeField> EqEvaluations<F> {
+    pub fn new(r: &[F]) -> Self {
+        Self {
+            r: r.to_vec(),
+            prefix: vec![F::one(); r.len() + 1],
+            index: 0,
+        }
+    }
+}
+
+impl<F: PrimeField> Iterator for EqEvaluations<F> {
+    type Item = F;
+
+    fn next(&mut self) -> Option<F> {
+        let n = self.r.len();
+        if self.index >= 1 << n {
+            return None;
+        }
+
+        // Only the variables from the highest flipped bit onwards change
+        let first_changed = if self.index == 0 {
+            0
+        } else {
+            let flipped = self.index ^ (self.index - 1);
+            n - (usize::BITS - flipped.leading_zeros()) as usize
+        };
+
+        for k in first_changed..n {
+            let bit = (self.index >> (n - 1 - k)) & 1;
+            let factor = if bit == 1 { self.r[k] } else { F::one() - self.r[k] };
+            self.prefix[k + 1] = self.prefix[k] * factor;
+        }
+
+        self.index += 1;
+        Some(self.prefix[n])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (1 << self.r.len()) - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<F: PrimeField> ExactSizeIterator for EqEvaluations<F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr;
+
+    type F = Fr;
+
+    fn point() -> Vec<F> {
+        vec![F::from(3), F::from(5), -F::from(2)]
+    }
+
+    #[test]
+    fn test_eq_poly_on_hypercube() {
+        let poly = eq_poly(&[F::from(0), F::from(1)]);
+
+        // Only the index of (0, 1) is set, the first variable being the most significant bit
+        assert_eq!(poly.evaluations, vec![F::from(0), F::from(1), F::from(0), F::from(0)]);
+    }
+
+    #[test]
+    fn test_eq_poly_matches_eq_eval() {
+        let r = point();
+        let poly = eq_poly(&r);
+        let x = vec![F::from(7), F::from(11), F::from(13)];
+
+        assert_eq!(poly.eval_full(&x), eq_eval(&x, &r));
+        assert_eq!(poly.evaluations.iter().sum::<F>(), F::from(1));
+        assert_eq!(eq_eval(&r, &r), r.iter().map(|&r_i| r_i * r_i + (F::from(1) - r_i) * (F::from(1) - r_i)).product());
+    }
+
+    #[test]
+    fn test_eq_poly_ordering_matches_partial_eval() {
+        let r = point();
+        let poly = eq_poly(&r);
+
+        // Fixing the first variable at r_0 multiplies by eq(r_0, r_0) and leaves eq over the rest
+        let fixed = poly.partial_eval(r[0], 0);
+        let rest = eq_poly(&r[1..]);
+        let factor = eq_eval(&r[..1], &r[..1]);
+        assert_eq!(fixed.evaluations, rest.evaluations.iter().map(|e| *e * factor).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_streaming_matches_table() {
+        for n in 0..5 {
+            let r: Vec<F> = (0..n).map(|i| F::from(i as u64 + 2)).collect();
+            let streamed: Vec<F> = EqEvaluations::new(&r).collect();
+
+            assert_eq!(streamed, eq_poly(&r).evaluations);
+            assert_eq!(EqEvaluations::new(&r).len(), 1 << n);
+        }
+    }
+}
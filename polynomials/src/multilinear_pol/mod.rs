@@ -1,2 +1,3 @@
 pub mod multilinear_poly;
-pub mod utiles;
\ No newline at end of file
+pub mod utiles;
+pub mod eq_poly;
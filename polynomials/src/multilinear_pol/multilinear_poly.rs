@@ -1,8 +1,10 @@
 
 use ark_ff::Field;
-use ark_ff::{BigInteger, PrimeField};
+use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use std::ops::{Add, AddAssign};
+use fiat_shamir_transcript::absorb::Absorb;
+use crate::error::PolynomialError;
+use std::ops::AddAssign;
 
 
 use super::utiles::generate_pairs;
@@ -21,19 +23,32 @@ pub struct MultiLinearPolynomial<F: Field> {
 }
 
 impl <F: PrimeField> MultiLinearPolynomial<F> {
-    pub fn new( _variables: usize, evaluations: Vec<F>) -> Self{
-        
-        // Also checks that the length is not zero 
-        assert!(evaluations.len() > 0 as usize, "evaluations cannot be zero");
-
-        // this check that the length of the evaluations is equal to 2 to the power of number of variables
-        assert!(evaluations.len() == (2usize.pow(_variables as u32)), "wrong length of eval");
+    /// Creates the polynomial from its evaluations over the boolean hypercube.
+    ///
+    /// # Panics
+    ///
+    /// If the evaluations are not `2^variables` values; see [`Self::try_new`].
+    pub fn new(variables: usize, evaluations: Vec<F>) -> Self {
+        Self::try_new(variables, evaluations).unwrap_or_else(|error| panic!("{}", error))
+    }
 
+    pub fn try_new(variables: usize, evaluations: Vec<F>) -> Result<Self, PolynomialError> {
+        if evaluations.is_empty() {
+            return Err(PolynomialError::EmptyEvaluations);
+        }
 
-        Self {
-            variables: _variables,
-            evaluations: evaluations
+        // The length of the evaluations must be 2 to the power of the number of variables
+        if variables >= usize::BITS as usize || evaluations.len() != 1 << variables {
+            return Err(PolynomialError::WrongEvaluationsLength {
+                variables,
+                len: evaluations.len(),
+            });
         }
+
+        Ok(Self {
+            variables,
+            evaluations,
+        })
     }
 
     pub fn zero(num_vars: usize) -> Self {
@@ -42,6 +57,7 @@ impl <F: PrimeField> MultiLinearPolynomial<F> {
 
 
 
+    #[allow(clippy::unnecessary_cast)]
     pub fn add(&self, other: Self) -> Self {
 
         let eval_1_len = self.evaluations.len();
@@ -117,27 +133,25 @@ impl <F: PrimeField> MultiLinearPolynomial<F> {
         }
     }
 
-    pub fn partial_evaluations(&self, evaluation_points: Vec<F>, variable_indices: Vec<usize>) -> Self {
-        let mut eval_polynomial = self.clone();
-
+    pub fn partial_evaluations(&self, evaluation_points: Vec<F>, variable_indices: Vec<usize>) -> Result<Self, PolynomialError> {
         if evaluation_points.len() != variable_indices.len() {
-            panic!(
-                "The length of evaluation_points and variable_indices should be the same: {}, {}",
-                evaluation_points.len(),
-                variable_indices.len()
-            );
+            return Err(PolynomialError::PartialEvaluationMismatch {
+                points: evaluation_points.len(),
+                indices: variable_indices.len(),
+            });
         }
 
-        for i in 0..evaluation_points.len() {
-            eval_polynomial =
-                eval_polynomial.partial_eval(evaluation_points[i], variable_indices[i]);
+        let mut eval_polynomial = self.clone();
+        for (point, index) in evaluation_points.into_iter().zip(variable_indices) {
+            eval_polynomial = eval_polynomial.partial_eval(point, index);
         }
 
-        eval_polynomial
+        Ok(eval_polynomial)
     }
 
     
 
+    #[allow(unused_assignments, clippy::let_and_return)]
     pub fn eval_full(&self, eval_points: &[F]) -> F {
         assert_eq!(eval_points.len(), self.variables, "Length of eval_points must equal number_of_variables");
 
@@ -154,28 +168,20 @@ impl <F: PrimeField> MultiLinearPolynomial<F> {
 
 }
 
-impl<F: PrimeField> MultiLinearPolynomial<F> {
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::new();
-
-        // Append the number of variables as a single byte (assuming < 256 variables)
-        bytes.push(self.variables as u8);
-
-        // Append each evaluation as a fixed size byte array
-        for eval in &self.evaluations {
-            let big_int = eval.into_bigint().to_bytes_be();
-            bytes.extend_from_slice(&big_int);
-        }
-
-        bytes
+// The number of variables, then the length-prefixed evaluations
+impl<F: PrimeField> Absorb for MultiLinearPolynomial<F> {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&(self.variables as u64).to_le_bytes());
+        self.evaluations.as_slice().write_transcript_bytes(bytes);
     }
 }
 
 impl<F: PrimeField> AddAssign for MultiLinearPolynomial<F> {
     fn add_assign(&mut self, other: Self) {
     
+        // An operator cannot report an error, so a mismatch is a bug of the caller
         if self.variables != other.variables {
-            panic!("The number of variables in the two polynomials must be the same");
+            panic!("{}", PolynomialError::VariableMismatch { expected: self.variables, actual: other.variables });
         }
 
         for i in 0..self.evaluations.len() {
@@ -198,6 +204,7 @@ mod tests {
     use ark_test_curves::bls12_381::Fr;
 
     type F = Fr;
+    #[allow(non_camel_case_types)]
     type poly = MultiLinearPolynomial<F>;
 
 
@@ -242,5 +249,38 @@ mod tests {
 
     }
 
+    #[test]
+    fn test_try_new() {
+        assert_eq!(poly::try_new(1, vec![]), Err(PolynomialError::EmptyEvaluations));
+        assert_eq!(
+            poly::try_new(2, vec![F::from(1), F::from(2)]),
+            Err(PolynomialError::WrongEvaluationsLength { variables: 2, len: 2 })
+        );
+        assert!(poly::try_new(1, vec![F::from(1), F::from(2)]).is_ok());
+    }
+
+    #[test]
+    fn test_partial_evaluations_length_mismatch() {
+        let polynomial = poly::new(2, vec![F::from(0), F::from(0), F::from(2), F::from(5)]);
+
+        assert_eq!(
+            polynomial.partial_evaluations(vec![F::from(3)], vec![0, 0]),
+            Err(PolynomialError::PartialEvaluationMismatch { points: 1, indices: 2 })
+        );
+        assert_eq!(polynomial.partial_evaluations(vec![F::from(3)], vec![0]), Ok(polynomial.partial_eval(F::from(3), 0)));
+    }
+
+    #[test]
+    fn test_transcript_bytes() {
+        let polynomial = poly::new(1, vec![F::from(1), F::from(2)]);
+        let bytes = polynomial.to_transcript_bytes();
+
+        // The number of variables, the number of evaluations, then the evaluations
+        assert_eq!(bytes.len(), 8 + 8 + 2 * 32);
+        assert_eq!(&bytes[..8], &1u64.to_le_bytes());
+        assert_eq!(&bytes[8..16], &2u64.to_le_bytes());
+        assert_eq!(bytes[16..].to_vec(), [F::from(1), F::from(2)].as_slice().to_transcript_bytes()[8..].to_vec());
+    }
+
 
 }
\ No newline at end of file
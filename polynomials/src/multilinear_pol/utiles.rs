@@ -1,3 +1,4 @@
+#[allow(clippy::manual_is_multiple_of)]
 pub fn generate_pairs(total_evaluations: usize, index: usize) -> Vec<(usize, usize)> {
     // Ensure total_evaluations is even
     assert!(total_evaluations % 2 == 0, "total_evaluations must be even");
@@ -0,0 +1,244 @@
+use ark_ff::FftField;
+use crate::error::PolynomialError;
+
+/// The multiplicative subgroup $H = \{1, \omega, \ldots, \omega^{n - 1}\}$ of the n-th roots
+/// of unity, n a power of two, over which polynomials of degree less than n are converted
+/// between coefficient and evaluation form with a radix-2 NTT in $O(n \log n)$.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvaluationDomain<F: FftField> {
+    size: usize,
+    // omega, a primitive `size`-th root of unity, and its inverse
+    group_gen: F,
+    group_gen_inv: F,
+    size_inv: F,
+}
+
+impl<F: FftField> EvaluationDomain<F> {
+    /// The smallest domain holding at least `min_size` elements.
+    pub fn new(min_size: usize) -> Result<Self, PolynomialError> {
+        let too_large = |size| PolynomialError::DomainTooLarge {
+            size,
+            two_adicity: F::TWO_ADICITY,
+        };
+        // When the power of two does not fit in a usize, `min_size` itself is reported
+        let size = min_size.max(1).checked_next_power_of_two().ok_or(too_large(min_size))?;
+
+        let group_gen = F::get_root_of_unity(size as u64).ok_or(too_large(size))?;
+        Ok(Self {
+            size,
+            group_gen,
+            group_gen_inv: group_gen.inverse().ok_or(too_large(size))?,
+            size_inv: F::from(size as u64).inverse().ok_or(too_large(size))?,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn log_size(&self) -> u32 {
+        self.size.trailing_zeros()
+    }
+
+    pub fn group_gen(&self) -> F {
+        self.group_gen
+    }
+
+    /// $\omega^i$
+    pub fn element(&self, i: usize) -> F {
+        self.group_gen.pow([i as u64])
+    }
+
+    /// The elements of the domain in the order of the evaluations.
+    pub fn elements(&self) -> Vec<F> {
+        let mut elements = Vec::with_capacity(self.size);
+        let mut element = F::one();
+        for _ in 0..self.size {
+            elements.push(element);
+            element *= self.group_gen;
+        }
+        elements
+    }
+
+    /// $Z_H(x) = x^n - 1$, zero exactly on the domain.
+    pub fn evaluate_vanishing_polynomial(&self, x: F) -> F {
+        x.pow([self.size as u64]) - F::one()
+    }
+
+    /// Evaluates the polynomial with the given coefficients over the domain.
+    pub fn fft(&self, coefficients: &[F]) -> Vec<F> {
+        assert!(coefficients.len() <= self.size, "More coefficients than elements in the domain");
+
+        let mut values = coefficients.to_vec();
+        values.resize(self.size, F::zero());
+        radix2_ntt(&mut values, self.group_gen);
+        values
+    }
+
+    /// Interpolates the coefficients of the polynomial taking `evaluations` over the domain,
+    /// one evaluation per element: unlike missing coefficients, missing evaluations are not zeros.
+    pub fn ifft(&self, evaluations: &[F]) -> Vec<F> {
+        assert_eq!(evaluations.len(), self.size, "There must be one evaluation per element of the domain");
+
+        let mut values = evaluations.to_vec();
+        radix2_ntt(&mut values, self.group_gen_inv);
+        for value in values.iter_mut() {
+            *value *= self.size_inv;
+        }
+        values
+    }
+
+    /// Evaluates the polynomial over the coset $g H$: $p(g x)$ has coefficients $c_i g^i$.
+    pub fn coset_fft(&self, coefficients: &[F], offset: F) -> Vec<F> {
+        self.fft(&scale_by_powers(coefficients, offset))
+    }
+
+    /// Interpolates the coefficients of the polynomial taking `evaluations` over the coset $g H$,
+    /// one evaluation per element.
+    pub fn coset_ifft(&self, evaluations: &[F], offset: F) -> Vec<F> {
+        let offset_inv = offset.inverse().expect("The coset offset must be non-zero");
+        scale_by_powers(&self.ifft(evaluations), offset_inv)
+    }
+}
+
+// c_i * g^i
+fn scale_by_powers<F: FftField>(values: &[F], g: F) -> Vec<F> {
+    let mut power = F::one();
+    values
+        .iter()
+        .map(|value| {
+            let scaled = *value * power;
+            power *= g;
+            scaled
+        })
+        .collect()
+}
+
+// In-place iterative Cooley-Tukey: after the bit-reversal permutation, every stage merges
+// the transforms of the even and odd halves of blocks of twice the previous size.
+fn radix2_ntt<F: FftField>(values: &mut [F], root: F) {
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+    let log_n = n.trailing_zeros();
+
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut half = 1;
+    while half < n {
+        // A primitive (2 * half)-th root of unity, and its powers for the butterflies
+        let stage_root = root.pow([(n / (2 * half)) as u64]);
+        let mut twiddles = Vec::with_capacity(half);
+        let mut twiddle = F::one();
+        for _ in 0..half {
+            twiddles.push(twiddle);
+            twiddle *= stage_root;
+        }
+
+        for block in values.chunks_exact_mut(2 * half) {
+            let (even, odd) = block.split_at_mut(half);
+            for ((e, o), w) in even.iter_mut().zip(odd.iter_mut()).zip(&twiddles) {
+                let t = *o * w;
+                *o = *e - t;
+                *e += t;
+            }
+        }
+        half *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::Field;
+    use ark_test_curves::bls12_381::{Fq, Fr};
+
+    type F = Fr;
+
+    fn naive_evaluate(coefficients: &[F], x: F) -> F {
+        coefficients.iter().rev().fold(F::from(0), |acc, c| acc * x + c)
+    }
+
+    fn coefficients(len: u64) -> Vec<F> {
+        (0..len).map(|i| F::from(i * i + 3)).collect()
+    }
+
+    #[test]
+    fn test_domain() {
+        let domain = EvaluationDomain::<F>::new(5).unwrap();
+
+        assert_eq!(domain.size(), 8);
+        assert_eq!(domain.log_size(), 3);
+        assert_eq!(domain.group_gen().pow([8]), F::from(1));
+        assert_ne!(domain.group_gen().pow([4]), F::from(1));
+        assert_eq!(domain.elements()[5], domain.element(5));
+        assert_eq!(EvaluationDomain::<F>::new(0).unwrap().size(), 1);
+
+        assert!(domain.elements().into_iter().all(|x| domain.evaluate_vanishing_polynomial(x) == F::from(0)));
+        assert_eq!(domain.evaluate_vanishing_polynomial(F::from(2)), F::from(255));
+    }
+
+    #[test]
+    fn test_domain_too_large() {
+        assert_eq!(
+            EvaluationDomain::<F>::new(1 << 33),
+            Err(PolynomialError::DomainTooLarge { size: 1 << 33, two_adicity: 32 })
+        );
+        // The base field only has the square roots of unity
+        assert!(EvaluationDomain::<Fq>::new(2).is_ok());
+
+        // The next power of two overflows
+        assert_eq!(
+            EvaluationDomain::<F>::new(usize::MAX),
+            Err(PolynomialError::DomainTooLarge { size: usize::MAX, two_adicity: 32 })
+        );
+        assert!(EvaluationDomain::<Fq>::new(4).is_err());
+    }
+
+    #[test]
+    fn test_fft_matches_evaluation() {
+        let domain = EvaluationDomain::<F>::new(16).unwrap();
+        let coefficients = coefficients(13);
+        let evaluations = domain.fft(&coefficients);
+
+        for (x, y) in domain.elements().into_iter().zip(&evaluations) {
+            assert_eq!(naive_evaluate(&coefficients, x), *y);
+        }
+    }
+
+    #[test]
+    fn test_ifft_inverts_fft() {
+        for size in [1, 2, 4, 32] {
+            let domain = EvaluationDomain::<F>::new(size).unwrap();
+            let coefficients = coefficients(size as u64);
+
+            assert_eq!(domain.ifft(&domain.fft(&coefficients)), coefficients);
+        }
+    }
+
+    #[test]
+    fn test_coset_fft() {
+        let domain = EvaluationDomain::<F>::new(8).unwrap();
+        let offset = F::GENERATOR;
+        let coefficients = coefficients(8);
+        let evaluations = domain.coset_fft(&coefficients, offset);
+
+        for (x, y) in domain.elements().into_iter().zip(&evaluations) {
+            assert_eq!(naive_evaluate(&coefficients, offset * x), *y);
+        }
+        assert_eq!(domain.coset_ifft(&evaluations, offset), coefficients);
+    }
+
+    #[test]
+    #[should_panic(expected = "There must be one evaluation per element of the domain")]
+    fn test_ifft_missing_evaluations() {
+        let domain = EvaluationDomain::<F>::new(8).unwrap();
+        domain.ifft(&coefficients(5));
+    }
+}
@@ -1 +1,2 @@
-pub mod univariat_polynomial;
\ No newline at end of file
+pub mod univariat_polynomial;
+pub mod evaluation_domain;
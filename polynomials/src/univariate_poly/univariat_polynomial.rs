@@ -1,9 +1,18 @@
 
-use ark_ff::Field;
-use ark_ff::PrimeField;
+use std::ops::{Add, Mul, Neg, Sub};
 
+use ark_ff::{Field, PrimeField, Zero};
+use fiat_shamir_transcript::absorb::Absorb;
+use crate::error::PolynomialError;
+use super::evaluation_domain::EvaluationDomain;
 
-#[derive(Clone, Debug, PartialEq)]
+/// Products of polynomials with at least this many coefficients each go through the NTT
+/// instead of the schoolbook product.
+pub const FFT_MUL_THRESHOLD: usize = 64;
+
+/// A polynomial in coefficient form, lowest degree first. The coefficients never end
+/// with zeros, so the zero polynomial has none and equal polynomials have equal coefficients.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct UnivariantPolynomial<F: Field> {
     coefficients: Vec<F>,
 }
@@ -12,56 +21,65 @@ impl <F: PrimeField> UnivariantPolynomial<F> {
     
     pub fn new(coefficient: Vec<F>) -> Self{
 
-        Self { coefficients: coefficient }
+        Self { coefficients: trim(coefficient) }
     }
 
-    fn coefficient_len (&self) -> usize {
-        self.coefficients.len()
+    pub fn zero() -> Self {
+        Self::new(Vec::new())
     }
 
-    fn check_zero_len(&self) -> bool {
-        if self.coefficient_len() == 0 {
-            true
-        } else {
-            false
-        }
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
     }
 
-    pub fn add (&self, other: Self) -> Self{
-
-        // Check if the any of them has zero length coefficient 
-        
-        if self.check_zero_len() {
-            return other 
-        }
-
-        if other.check_zero_len() {
-            return self.clone();
-        }
+    /// `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
 
-        if self.check_zero_len() != other.check_zero_len() {
-            panic!("Cannot add two polynomials of different length")
-        }
+    /// Interpolates the polynomial of degree less than the domain size taking `evaluations` over `domain`,
+    /// which must hold one evaluation per element of the domain.
+    pub fn from_evaluations(domain: &EvaluationDomain<F>, evaluations: &[F]) -> Self {
+        Self::new(domain.ifft(evaluations))
+    }
 
+    /// The evaluations of the polynomial over `domain`, which must be larger than its degree.
+    pub fn evaluate_over_domain(&self, domain: &EvaluationDomain<F>) -> Vec<F> {
+        domain.fft(&self.coefficients)
+    }
 
-            let mut new_coefficient = self.coefficients.clone();
-            for i in 0..self.coefficient_len() {
+    pub fn coefficients(&self) -> &[F] {
+        &self.coefficients
+    }
 
-                new_coefficient[i] += other.coefficients[i];
-            }
+    /// Same as `self + other`.
+    pub fn add(&self, other: Self) -> Self {
+        self + other
+    }
 
-            return Self::new(new_coefficient);
-      
-        
+    /// Same as `self * other`.
+    pub fn mul(&self, other: Self) -> Self {
+        self * other
     }
 
+    fn coefficient_len (&self) -> usize {
+        self.coefficients.len()
+    }
 
-    pub fn mul(&self, other: Self) -> Self {
-        // If either polynomial is zero, return a zero polynomial
-        if self.check_zero_len() || other.check_zero_len() {
-            return Self::new(Vec::new());
+    // Coefficient-wise `self + sign * other`, whatever the two lengths
+    fn add_scaled(&self, other: &Self, sign: F) -> Self {
+        let mut new_coefficient = self.coefficients.clone();
+        if new_coefficient.len() < other.coefficient_len() {
+            new_coefficient.resize(other.coefficient_len(), F::zero());
+        }
+        for (coefficient, other_coefficient) in new_coefficient.iter_mut().zip(&other.coefficients) {
+            *coefficient += sign * other_coefficient;
         }
 
+        Self::new(new_coefficient)
+    }
+
+    fn schoolbook_mul(&self, other: &Self) -> Self {
         // Degree of the resulting polynomial
         let first_degree = self.coefficient_len();
         let second_degree = other.coefficient_len();
@@ -79,8 +97,30 @@ impl <F: PrimeField> UnivariantPolynomial<F> {
 
         // Return the resulting polynomial
         Self::new(coefficient_res)
-    
-}
+    }
+
+    /// The product through pointwise multiplication over a domain larger than its degree,
+    /// or the schoolbook product when the two-adicity of the field is too small for one.
+    pub fn fft_mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+
+        let product_len = self.coefficient_len() + other.coefficient_len() - 1;
+        let domain = match EvaluationDomain::new(product_len) {
+            Ok(domain) => domain,
+            Err(_) => return self.schoolbook_mul(other),
+        };
+
+        let evaluations: Vec<F> = self
+            .evaluate_over_domain(&domain)
+            .into_iter()
+            .zip(other.evaluate_over_domain(&domain))
+            .map(|(a, b)| a * b)
+            .collect();
+
+        Self::new(domain.ifft(&evaluations))
+    }
 
 
 pub fn evaluate(&self, eval: F) -> F {
@@ -95,13 +135,237 @@ pub fn evaluate(&self, eval: F) -> F {
     res
 }
 
+    /// Long division: the quotient and remainder of `self` by `divisor`, the remainder having
+    /// a lower degree than `divisor`.
+    pub fn divide_with_remainder(&self, divisor: &Self) -> Result<(Self, Self), PolynomialError> {
+        let divisor = &divisor.coefficients;
+        let leading_inv = divisor.last().ok_or(PolynomialError::DivisionByZero)?.inverse().expect("trimmed, so non-zero");
+
+        let mut remainder = self.coefficients.clone();
+        if remainder.len() < divisor.len() {
+            return Ok((Self::zero(), self.clone()));
+        }
 
+        // Cancel the leading coefficient of the remainder, from the top down
+        let mut quotient = vec![F::zero(); remainder.len() - divisor.len() + 1];
+        for i in (0..quotient.len()).rev() {
+            let coefficient = remainder[i + divisor.len() - 1] * leading_inv;
+            quotient[i] = coefficient;
+            for (j, d) in divisor.iter().enumerate() {
+                remainder[i + j] -= coefficient * d;
+            }
+        }
+        remainder.truncate(divisor.len() - 1);
+
+        Ok((Self::new(quotient), Self::new(remainder)))
+    }
 
+    /// Same as [`Self::divide_with_remainder`] in the time of a few multiplications: with
+    /// $\text{rev}_k(p) = X^k p(1/X)$, the reversed quotient is
+    /// $\text{rev}(a) \cdot \text{rev}(b)^{-1} \bmod X^{\deg a - \deg b + 1}$.
+    pub fn fast_divide_with_remainder(&self, divisor: &Self) -> Result<(Self, Self), PolynomialError> {
+        if divisor.is_zero() {
+            return Err(PolynomialError::DivisionByZero);
+        }
+        if self.coefficient_len() < divisor.coefficient_len() {
+            return Ok((Self::zero(), self.clone()));
+        }
+
+        let quotient_len = self.coefficient_len() - divisor.coefficient_len() + 1;
+        let reversed_dividend = Self::new(self.coefficients.iter().rev().take(quotient_len).cloned().collect());
+        let reversed_divisor = Self::new(divisor.coefficients.iter().rev().cloned().collect());
+
+        let mut quotient = (reversed_dividend * reversed_divisor.reciprocal(quotient_len)).coefficients;
+        quotient.resize(quotient_len, F::zero());
+        quotient.reverse();
+        let quotient = Self::new(quotient);
+
+        // Only the coefficients below the degree of the divisor are left
+        let mut remainder = (self - &(divisor * &quotient)).coefficients;
+        remainder.truncate(divisor.coefficient_len() - 1);
+
+        Ok((quotient, Self::new(remainder)))
+    }
+
+    // The inverse modulo X^k of a polynomial with a non-zero constant term, with Newton's
+    // iteration g <- g (2 - f g), which doubles the number of correct coefficients every step.
+    fn reciprocal(&self, k: usize) -> Self {
+        let mut reciprocal = Self::new(vec![self.coefficients[0].inverse().expect("The constant term must be non-zero")]);
+        let mut precision = 1;
+
+        while precision < k {
+            precision = (2 * precision).min(k);
+
+            let truncated = Self::new(self.coefficients.iter().take(precision).cloned().collect());
+            let error = Self::new(vec![F::from(2u64)]) - (&truncated * &reciprocal).truncated(precision);
+            reciprocal = (&reciprocal * &error).truncated(precision);
+        }
+
+        reciprocal
+    }
+
+    // The polynomial modulo X^len
+    fn truncated(mut self, len: usize) -> Self {
+        self.coefficients.truncate(len);
+        Self::new(self.coefficients)
+    }
+
+    /// $Z_H(X) = X^n - 1$, which vanishes exactly on the domain of size n.
+    pub fn vanishing_polynomial(domain: &EvaluationDomain<F>) -> Self {
+        let mut coefficients = vec![F::zero(); domain.size() + 1];
+        coefficients[0] = -F::one();
+        coefficients[domain.size()] = F::one();
+        Self::new(coefficients)
+    }
+
+    /// The quotient and remainder by the vanishing polynomial of `domain` in linear time,
+    /// since $X^{n + i} = X^i \bmod X^n - 1$.
+    pub fn divide_by_vanishing_poly(&self, domain: &EvaluationDomain<F>) -> (Self, Self) {
+        let n = domain.size();
+        let mut remainder = self.coefficients.clone();
+        if remainder.len() <= n {
+            return (Self::zero(), self.clone());
+        }
+
+        let mut quotient = vec![F::zero(); remainder.len() - n];
+        for i in (n..remainder.len()).rev() {
+            let coefficient = remainder[i];
+            quotient[i - n] = coefficient;
+            remainder[i - n] += coefficient;
+        }
+        remainder.truncate(n);
+
+        (Self::new(quotient), Self::new(remainder))
+    }
+
+
+
+}
+
+// Drops the zero coefficients of the highest degrees
+fn trim<F: PrimeField>(mut coefficients: Vec<F>) -> Vec<F> {
+    while coefficients.last().is_some_and(|c| c.is_zero()) {
+        coefficients.pop();
+    }
+    coefficients
+}
+
+impl<F: PrimeField> Zero for UnivariantPolynomial<F> {
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+impl<F: PrimeField> Add<&UnivariantPolynomial<F>> for &UnivariantPolynomial<F> {
+    type Output = UnivariantPolynomial<F>;
+
+    fn add(self, other: &UnivariantPolynomial<F>) -> Self::Output {
+        self.add_scaled(other, F::one())
+    }
+}
+
+impl<F: PrimeField> Sub<&UnivariantPolynomial<F>> for &UnivariantPolynomial<F> {
+    type Output = UnivariantPolynomial<F>;
+
+    fn sub(self, other: &UnivariantPolynomial<F>) -> Self::Output {
+        self.add_scaled(other, -F::one())
+    }
+}
+
+impl<F: PrimeField> Mul<&UnivariantPolynomial<F>> for &UnivariantPolynomial<F> {
+    type Output = UnivariantPolynomial<F>;
+
+    fn mul(self, other: &UnivariantPolynomial<F>) -> Self::Output {
+        if self.is_zero() || other.is_zero() {
+            return UnivariantPolynomial::zero();
+        }
+
+        if self.coefficient_len().min(other.coefficient_len()) >= FFT_MUL_THRESHOLD {
+            self.fft_mul(other)
+        } else {
+            self.schoolbook_mul(other)
+        }
+    }
+}
+
+impl<F: PrimeField> Mul<F> for &UnivariantPolynomial<F> {
+    type Output = UnivariantPolynomial<F>;
+
+    fn mul(self, scalar: F) -> Self::Output {
+        UnivariantPolynomial::new(self.coefficients.iter().map(|c| *c * scalar).collect())
+    }
+}
+
+impl<F: PrimeField> Mul<F> for UnivariantPolynomial<F> {
+    type Output = Self;
+
+    fn mul(self, scalar: F) -> Self {
+        &self * scalar
+    }
+}
+
+impl<F: PrimeField> Neg for &UnivariantPolynomial<F> {
+    type Output = UnivariantPolynomial<F>;
+
+    fn neg(self) -> Self::Output {
+        UnivariantPolynomial::new(self.coefficients.iter().map(|c| -*c).collect())
+    }
+}
+
+impl<F: PrimeField> Neg for UnivariantPolynomial<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        -&self
+    }
+}
+
+// The owned operators borrow their operands
+macro_rules! impl_owned_op {
+    ($op:ident, $method:ident) => {
+        impl<F: PrimeField> $op for UnivariantPolynomial<F> {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self {
+                $op::$method(&self, &other)
+            }
+        }
+
+        impl<F: PrimeField> $op<&UnivariantPolynomial<F>> for UnivariantPolynomial<F> {
+            type Output = Self;
+
+            fn $method(self, other: &Self) -> Self {
+                $op::$method(&self, other)
+            }
+        }
+
+        impl<F: PrimeField> $op<UnivariantPolynomial<F>> for &UnivariantPolynomial<F> {
+            type Output = UnivariantPolynomial<F>;
+
+            fn $method(self, other: UnivariantPolynomial<F>) -> Self::Output {
+                $op::$method(self, &other)
+            }
+        }
+    };
+}
+
+impl_owned_op!(Add, add);
+impl_owned_op!(Sub, sub);
+impl_owned_op!(Mul, mul);
+
+impl<F: PrimeField> Absorb for UnivariantPolynomial<F> {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        self.coefficients.as_slice().write_transcript_bytes(bytes);
+    }
 }
 
 
 /// A vector containing the coefficients of the Lagrange basis polynomial \( L_i(x) \).
-fn lagrange_basis<F: PrimeField>(i: usize, x_coordinates: &[F]) -> Vec<F> {
+fn lagrange_basis<F: PrimeField>(i: usize, x_coordinates: &[F]) -> Result<Vec<F>, PolynomialError> {
     // Get the \( i \)-th \( x \) coordinate
     let x_i = x_coordinates[i];
     // Initialize the numerator polynomial with 1
@@ -129,20 +393,27 @@ fn lagrange_basis<F: PrimeField>(i: usize, x_coordinates: &[F]) -> Vec<F> {
         }
     }
 
-    // Compute the inverse of the denominator
-    let den_inv = den.inverse().unwrap();
+    // Compute the inverse of the denominator, which is zero when an x coordinate repeats
+    let den_inv = den.inverse().ok_or(PolynomialError::DuplicateInterpolationPoint)?;
     // Normalize the coefficients of the numerator polynomial by dividing by the denominator
     for coeff in num.iter_mut() {
         *coeff *= den_inv;
     }
 
     // Return the coefficients of the Lagrange basis polynomial
-    num
+    Ok(num)
 }
 
 
 /// A vector containing the coefficients of the Lagrange interpolating polynomial.
-pub fn lagrange_interpolate<F: PrimeField>(x_coordinates: &[F], y_coordinates: &[F]) -> Vec<F> {
+pub fn lagrange_interpolate<F: PrimeField>(x_coordinates: &[F], y_coordinates: &[F]) -> Result<Vec<F>, PolynomialError> {
+    if x_coordinates.len() != y_coordinates.len() {
+        return Err(PolynomialError::CoordinatesMismatch {
+            x_len: x_coordinates.len(),
+            y_len: y_coordinates.len(),
+        });
+    }
+
     // Get the number of points
     let n = x_coordinates.len();
     // Initialize the result vector with zeros
@@ -151,7 +422,7 @@ pub fn lagrange_interpolate<F: PrimeField>(x_coordinates: &[F], y_coordinates: &
     // Iterate over each \( y \) coordinate
     for (i, &y_i) in y_coordinates.iter().enumerate() {
         // Compute the Lagrange basis polynomial for the \( i \)-th point
-        let l_i = lagrange_basis(i, x_coordinates);
+        let l_i = lagrange_basis(i, x_coordinates)?;
         // Accumulate the scaled basis polynomial into the result
         for (j, &coeff) in l_i.iter().enumerate() {
             result[j] += y_i * coeff;
@@ -159,19 +430,22 @@ pub fn lagrange_interpolate<F: PrimeField>(x_coordinates: &[F], y_coordinates: &
     }
 
     // Return the coefficients of the Lagrange interpolating polynomial
-    result
+    Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
 
  
+    use crate::error::PolynomialError;
     use crate::univariate_poly::univariat_polynomial::lagrange_interpolate;
 
-    use super::UnivariantPolynomial;
-    use ark_test_curves::bls12_381::Fr;
+    use super::{UnivariantPolynomial, FFT_MUL_THRESHOLD};
+    use crate::univariate_poly::evaluation_domain::EvaluationDomain;
+    use ark_test_curves::bls12_381::{Fq, Fr};
 
     type F = Fr;
+    #[allow(non_camel_case_types)]
     type poly = UnivariantPolynomial<F>;
 
     #[test]
@@ -183,6 +457,16 @@ mod tests {
         assert!(poly_1.add(poly_2) == poly::new(vec![F::from(5), F::from(7), F::from(9)]));
     }
 
+    #[test]
+    fn test_polynomial_addition_different_length() {
+
+        let poly_1 = poly::new(vec![F::from(1), F::from(2)]);
+        let poly_2 = poly::new(vec![F::from(4), F::from(5), F::from(6)]);
+
+        assert_eq!(poly_1.add(poly_2.clone()), poly::new(vec![F::from(5), F::from(7), F::from(6)]));
+        assert_eq!(poly_2.add(poly_1), poly::new(vec![F::from(5), F::from(7), F::from(6)]));
+    }
+
     #[test]
     fn test_polynomial_multiplication() {
 
@@ -192,6 +476,189 @@ mod tests {
         assert_eq!(poly_1.mul(poly_2) , poly::new(vec![F::from(4), F::from(13), F::from(10)]));
     }
 
+    #[test]
+    fn test_addition_and_multiplication_operators() {
+
+        let poly_1 = poly::new(vec![F::from(1), F::from(2)]);
+        let poly_2 = poly::new(vec![F::from(4), F::from(5), F::from(6)]);
+        let sum = poly::new(vec![F::from(5), F::from(7), F::from(6)]);
+        let product = poly::new(vec![F::from(4), F::from(13), F::from(16), F::from(12)]);
+
+        assert_eq!(&poly_1 + &poly_2, sum);
+        assert_eq!(&poly_1 + poly_2.clone(), sum);
+        assert_eq!(poly_2.clone() + &poly_1, sum);
+        assert_eq!(poly_1.clone() + poly_2.clone(), poly_1.add(poly_2.clone()));
+
+        assert_eq!(&poly_1 * &poly_2, product);
+        assert_eq!(&poly_1 * poly_2.clone(), product);
+        assert_eq!(poly_2.clone() * &poly_1, product);
+        assert_eq!(poly_1.clone() * poly_2.clone(), poly_1.mul(poly_2));
+    }
+
+    #[test]
+    fn test_polynomial_subtraction_and_negation() {
+
+        let poly_1 = poly::new(vec![F::from(1), F::from(2)]);
+        let poly_2 = poly::new(vec![F::from(4), F::from(5), F::from(6)]);
+
+        assert_eq!(&poly_1 - &poly_2, poly::new(vec![-F::from(3), -F::from(3), -F::from(6)]));
+        assert_eq!(&poly_1 - &poly_2, -(&poly_2 - &poly_1));
+        assert_eq!(-&poly_1 + poly_1.clone(), poly::zero());
+
+        // The leading coefficients cancel out
+        let difference = poly_2.clone() - poly::new(vec![F::from(0), F::from(1), F::from(6)]);
+        assert_eq!(difference.coefficients(), &[F::from(4), F::from(4)]);
+        assert_eq!(difference.degree(), Some(1));
+    }
+
+    #[test]
+    fn test_scalar_multiplication() {
+
+        let poly = poly::new(vec![F::from(1), F::from(2), F::from(3)]);
+
+        assert_eq!(&poly * F::from(2), poly::new(vec![F::from(2), F::from(4), F::from(6)]));
+        assert!((poly * F::from(0)).is_zero());
+    }
+
+    #[test]
+    fn test_zero_and_degree() {
+
+        let zero = poly::new(vec![F::from(0), F::from(0)]);
+        let poly = poly::new(vec![F::from(1), F::from(2), F::from(0)]);
+
+        assert!(zero.is_zero());
+        assert_eq!(zero, poly::zero());
+        assert_eq!(zero.degree(), None);
+        assert_eq!(poly.degree(), Some(1));
+        assert_eq!(poly, poly::new(vec![F::from(1), F::from(2)]));
+        assert_ne!(poly, poly::new(vec![F::from(1)]));
+
+        assert_eq!(&poly + &zero, poly);
+        assert!((&poly * &zero).is_zero());
+        assert!((&zero * &poly).is_zero());
+    }
+
+    #[test]
+    fn test_fft_multiplication() {
+
+        let poly_1 = poly::new((0..100u64).map(|i| F::from(i * 7 + 1)).collect());
+        let poly_2 = poly::new((0..70u64).map(|i| F::from(i * i + 2)).collect());
+
+        // Just below the threshold `mul` is still the schoolbook product
+        let short = poly::new(poly_2.coefficients()[..FFT_MUL_THRESHOLD - 1].to_vec());
+        assert_eq!(poly_1.mul(short.clone()), poly_1.fft_mul(&short));
+
+        let product = poly_1.mul(poly_2.clone());
+        let x = F::from(11);
+        assert_eq!(product.coefficients().len(), 169);
+        assert_eq!(product.evaluate(x), poly_1.evaluate(x) * poly_2.evaluate(x));
+    }
+
+    #[test]
+    fn test_multiplication_without_large_domains() {
+
+        // The base field only has the square roots of unity
+        let poly_1 = UnivariantPolynomial::new((0..64u64).map(|i| Fq::from(i * 7 + 1)).collect());
+        let poly_2 = UnivariantPolynomial::new((0..70u64).map(|i| Fq::from(i * i + 2)).collect());
+
+        let product = &poly_1 * &poly_2;
+        let x = Fq::from(11);
+        assert_eq!(product.degree(), Some(132));
+        assert_eq!(product.evaluate(x), poly_1.evaluate(x) * poly_2.evaluate(x));
+        assert_eq!(poly_1.fft_mul(&poly_2), product);
+    }
+
+    #[test]
+    fn test_domain_conversions() {
+
+        let domain = EvaluationDomain::<F>::new(4).unwrap();
+        let poly = poly::new(vec![F::from(1), F::from(2), F::from(3)]);
+        let evaluations = poly.evaluate_over_domain(&domain);
+
+        assert_eq!(evaluations, domain.elements().into_iter().map(|x| poly.evaluate(x)).collect::<Vec<_>>());
+        assert_eq!(poly::from_evaluations(&domain, &evaluations), poly::new(vec![F::from(1), F::from(2), F::from(3), F::from(0)]));
+    }
+
+    fn check_division(dividend: &poly, divisor: &poly, quotient: &poly, remainder: &poly) {
+        let x = F::from(17);
+        assert_eq!(dividend.evaluate(x), divisor.evaluate(x) * quotient.evaluate(x) + remainder.evaluate(x));
+        assert!(remainder.coefficients().len() < divisor.coefficients().len());
+    }
+
+    #[test]
+    fn test_long_division() {
+
+        // (3X^2 + 2X + 1)(X + 4) + 5
+        let dividend = poly::new(vec![F::from(9), F::from(9), F::from(14), F::from(3)]);
+        let divisor = poly::new(vec![F::from(4), F::from(1), F::from(0)]);
+        let (quotient, remainder) = dividend.divide_with_remainder(&divisor).unwrap();
+
+        assert_eq!(quotient, poly::new(vec![F::from(1), F::from(2), F::from(3)]));
+        assert_eq!(remainder, poly::new(vec![F::from(5)]));
+
+        // A dividend of lower degree is its own remainder
+        let (quotient, remainder) = divisor.divide_with_remainder(&dividend).unwrap();
+        assert_eq!(quotient, poly::new(vec![]));
+        assert_eq!(remainder, poly::new(vec![F::from(4), F::from(1)]));
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+
+        let dividend = poly::new(vec![F::from(1), F::from(2)]);
+
+        assert_eq!(dividend.divide_with_remainder(&poly::new(vec![F::from(0)])), Err(PolynomialError::DivisionByZero));
+        assert_eq!(dividend.fast_divide_with_remainder(&poly::new(vec![])), Err(PolynomialError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_fast_division_matches_long_division() {
+
+        // Large enough for the products of the Newton iteration to go through the NTT
+        let dividend = poly::new((0..300u64).map(|i| F::from(i * i + 5)).collect());
+        for divisor_len in [1, 2, 7, 100, 299, 300] {
+            let divisor = poly::new((0..divisor_len).map(|i| F::from(3 * i + 1)).collect());
+
+            let (quotient, remainder) = dividend.fast_divide_with_remainder(&divisor).unwrap();
+            assert_eq!((quotient.clone(), remainder.clone()), dividend.divide_with_remainder(&divisor).unwrap());
+            check_division(&dividend, &divisor, &quotient, &remainder);
+        }
+    }
+
+    #[test]
+    fn test_fast_division_without_large_domains() {
+
+        // Over the base field the products of the Newton iteration fall back to the schoolbook product
+        let dividend = UnivariantPolynomial::new((0..300u64).map(|i| Fq::from(i * i + 5)).collect());
+        for divisor_len in [2, 100, 200] {
+            let divisor = UnivariantPolynomial::new((0..divisor_len).map(|i| Fq::from(3 * i + 1)).collect());
+
+            let (quotient, remainder) = dividend.fast_divide_with_remainder(&divisor).unwrap();
+            assert_eq!((quotient.clone(), remainder.clone()), dividend.divide_with_remainder(&divisor).unwrap());
+            assert_eq!(&(&quotient * &divisor) + &remainder, dividend);
+            assert!(remainder.degree() < divisor.degree());
+        }
+    }
+
+    #[test]
+    fn test_divide_by_vanishing_poly() {
+
+        let domain = EvaluationDomain::<F>::new(8).unwrap();
+        let vanishing = poly::vanishing_polynomial(&domain);
+        assert!(vanishing.evaluate_over_domain(&EvaluationDomain::new(16).unwrap()).iter().step_by(2).all(|y| *y == F::from(0)));
+
+        // t(X) Z_H(X) + r(X) with deg r < 8
+        let t = poly::new((0..13u64).map(|i| F::from(i + 2)).collect());
+        let r = poly::new((0..5u64).map(|i| F::from(7 * i + 1)).collect());
+        let dividend = t.mul(vanishing.clone()).add(r.clone());
+
+        assert_eq!(dividend.divide_by_vanishing_poly(&domain), (t, r));
+        assert_eq!(dividend.divide_by_vanishing_poly(&domain), dividend.divide_with_remainder(&vanishing).unwrap());
+
+        let (quotient, remainder) = vanishing.divide_by_vanishing_poly(&domain);
+        assert_eq!((quotient, remainder), (poly::new(vec![F::from(1)]), poly::new(vec![])));
+    }
+
     #[test]
     fn test_polynomial_evaluation() {
         
@@ -218,7 +685,20 @@ mod tests {
         // let coefficients = lagrange_interpolate(&x_coordinates, &y_coordinates);
         // assert_eq!(coefficients, expected_coefficients);
         
-        assert_eq!(lagrange_interpolate(&x_coordinates, &y_coordinates), expected_coefficients)
+        assert_eq!(lagrange_interpolate(&x_coordinates, &y_coordinates), Ok(expected_coefficients))
+    }
+
+    #[test]
+    fn test_interpolation_errors() {
+
+        let x_coordinates = vec![F::from(1u64), F::from(2u64), F::from(1u64)];
+        let y_coordinates = vec![F::from(6u64), F::from(17u64), F::from(34u64)];
+
+        assert_eq!(lagrange_interpolate(&x_coordinates, &y_coordinates), Err(PolynomialError::DuplicateInterpolationPoint));
+        assert_eq!(
+            lagrange_interpolate(&x_coordinates[..2], &y_coordinates),
+            Err(PolynomialError::CoordinatesMismatch { x_len: 2, y_len: 3 })
+        );
     }
 
 
@@ -0,0 +1 @@
+pub mod virtual_polynomial;
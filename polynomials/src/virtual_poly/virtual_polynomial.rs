@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use ark_ff::PrimeField;
+use crate::composed::composed_multilinear::ComposedMultilinearPolynomial;
+use crate::error::PolynomialError;
+use crate::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
+
+/// A sum of weighted products of multilinear polynomials, e.g. `c1 * A * B * eq + c2 * C * eq`.
+/// Every MLE is stored once in `mles` and the products refer to it by index,
+/// so an MLE shared by several products is neither copied nor evaluated twice.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VirtualPolynomial<F: PrimeField> {
+    pub num_variables: usize,
+    pub mles: Vec<Arc<MultiLinearPolynomial<F>>>,
+    // (coefficient, indices in `mles` of the factors)
+    pub products: Vec<(F, Vec<usize>)>,
+}
+
+impl<F: PrimeField> VirtualPolynomial<F> {
+    pub fn new(num_variables: usize) -> Self {
+        Self {
+            num_variables,
+            mles: Vec::new(),
+            products: Vec::new(),
+        }
+    }
+
+    /// Adds `mle` to the pool unless it is already there, and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// If `mle` has a different number of variables; see [`Self::try_add_mle`].
+    pub fn add_mle(&mut self, mle: Arc<MultiLinearPolynomial<F>>) -> usize {
+        self.try_add_mle(mle).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_add_mle(&mut self, mle: Arc<MultiLinearPolynomial<F>>) -> Result<usize, PolynomialError> {
+        self.check_variables(&mle)?;
+
+        match self.mles.iter().position(|existing| Arc::ptr_eq(existing, &mle)) {
+            Some(index) => Ok(index),
+            None => {
+                self.mles.push(mle);
+                Ok(self.mles.len() - 1)
+            }
+        }
+    }
+
+    /// Adds the term `coefficient * mles[0] * mles[1] * ...`.
+    ///
+    /// # Panics
+    ///
+    /// If `mles` is empty or one of them has a different number of variables;
+    /// see [`Self::try_add_product`].
+    pub fn add_product(&mut self, coefficient: F, mles: Vec<Arc<MultiLinearPolynomial<F>>>) {
+        self.try_add_product(coefficient, mles).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Leaves the polynomial unchanged when it returns an error.
+    pub fn try_add_product(&mut self, coefficient: F, mles: Vec<Arc<MultiLinearPolynomial<F>>>) -> Result<(), PolynomialError> {
+        if mles.is_empty() {
+            return Err(PolynomialError::NoPolynomials);
+        }
+        // Check every factor before any of them enters the pool
+        mles.iter().try_for_each(|mle| self.check_variables(mle))?;
+
+        let indices = mles.into_iter().map(|mle| self.add_mle(mle)).collect();
+        self.products.push((coefficient, indices));
+        Ok(())
+    }
+
+    fn check_variables(&self, mle: &MultiLinearPolynomial<F>) -> Result<(), PolynomialError> {
+        if mle.variables != self.num_variables {
+            return Err(PolynomialError::VariableMismatch {
+                expected: self.num_variables,
+                actual: mle.variables,
+            });
+        }
+        Ok(())
+    }
+
+    /// The degree of the polynomial in every single variable.
+    pub fn max_degree(&self) -> usize {
+        self.products.iter().map(|(_, indices)| indices.len()).max().unwrap_or(0)
+    }
+
+    pub fn evaluate(&self, point: &[F]) -> F {
+        assert_eq!(point.len(), self.num_variables, "Incorrect number of evaluation points");
+        let mle_evaluations: Vec<F> = self.mles.iter().map(|mle| mle.eval_full(point)).collect();
+        self.combine(|index| mle_evaluations[index])
+    }
+
+    pub fn partial_eval(&self, eval_point: F, idx: usize) -> Self {
+        Self {
+            num_variables: self.num_variables - 1,
+            mles: self.mles.iter().map(|mle| Arc::new(mle.partial_eval(eval_point, idx))).collect(),
+            products: self.products.clone(),
+        }
+    }
+
+    /// The values of the polynomial over the boolean hypercube.
+    pub fn evaluations(&self) -> Vec<F> {
+        (0..1 << self.num_variables)
+            .map(|i| self.combine(|index| self.mles[index].evaluations[i]))
+            .collect()
+    }
+
+    // Sums the weighted products, given the value of every MLE of the pool
+    fn combine(&self, mle_value: impl Fn(usize) -> F) -> F {
+        self.products
+            .iter()
+            .map(|(coefficient, indices)| *coefficient * indices.iter().map(|&index| mle_value(index)).product::<F>())
+            .sum()
+    }
+}
+
+// A single product with coefficient one
+impl<F: PrimeField> From<ComposedMultilinearPolynomial<F>> for VirtualPolynomial<F> {
+    fn from(poly: ComposedMultilinearPolynomial<F>) -> Self {
+        let mut virtual_poly = Self::new(poly.polys[0].variables);
+        // Every factor gets its own entry, even when two factors are equal
+        let indices = poly
+            .polys
+            .into_iter()
+            .map(|mle| {
+                virtual_poly.mles.push(Arc::new(mle));
+                virtual_poly.mles.len() - 1
+            })
+            .collect();
+        virtual_poly.products.push((F::one(), indices));
+        virtual_poly
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr;
+
+    type F = Fr;
+
+    fn mle(evaluations: [u64; 4]) -> Arc<MultiLinearPolynomial<F>> {
+        Arc::new(MultiLinearPolynomial::new(2, evaluations.iter().map(|&e| F::from(e)).collect()))
+    }
+
+    // 3 * A * B * E + 5 * C * E
+    fn create_test_poly() -> VirtualPolynomial<F> {
+        let (a, b, c, e) = (mle([0, 1, 2, 3]), mle([4, 1, 7, 2]), mle([1, 1, 0, 5]), mle([2, 0, 3, 1]));
+        let mut poly = VirtualPolynomial::new(2);
+        poly.add_product(F::from(3), vec![a, b, e.clone()]);
+        poly.add_product(F::from(5), vec![c, e]);
+        poly
+    }
+
+    #[test]
+    fn test_shared_mles_are_stored_once() {
+        let poly = create_test_poly();
+
+        assert_eq!(poly.mles.len(), 4);
+        assert_eq!(poly.products[0].1, vec![0, 1, 2]);
+        assert_eq!(poly.products[1].1, vec![3, 2]);
+        assert_eq!(poly.max_degree(), 3);
+    }
+
+    #[test]
+    fn test_evaluations() {
+        let poly = create_test_poly();
+
+        // 3 * [0, 0, 42, 6] + 5 * [2, 0, 0, 5]
+        assert_eq!(poly.evaluations(), vec![F::from(10), F::from(0), F::from(126), F::from(43)]);
+    }
+
+    #[test]
+    fn test_evaluate_matches_partial_evaluations() {
+        let poly = create_test_poly();
+        let point = [F::from(3), F::from(5)];
+
+        let expected = F::from(3) * poly.mles[0].eval_full(&point) * poly.mles[1].eval_full(&point) * poly.mles[2].eval_full(&point)
+            + F::from(5) * poly.mles[3].eval_full(&point) * poly.mles[2].eval_full(&point);
+        assert_eq!(poly.evaluate(&point), expected);
+
+        let fixed = poly.partial_eval(point[0], 0);
+        assert_eq!(fixed.num_variables, 1);
+        assert_eq!(fixed.evaluate(&point[1..]), expected);
+    }
+
+    #[test]
+    fn test_from_composed() {
+        let a = MultiLinearPolynomial::new(2, vec![F::from(0), F::from(1), F::from(2), F::from(3)]);
+        let composed = ComposedMultilinearPolynomial::new(vec![a.clone(), a]);
+        let poly = VirtualPolynomial::from(composed.clone());
+
+        assert_eq!(poly.max_degree(), 2);
+        assert_eq!(poly.evaluations(), composed.elementwise_product());
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a polynomial in 2 variables, got 1")]
+    fn test_different_variables() {
+        let mut poly = create_test_poly();
+        poly.add_product(F::from(1), vec![Arc::new(MultiLinearPolynomial::new(1, vec![F::from(1), F::from(2)]))]);
+    }
+
+    #[test]
+    fn test_try_add_product() {
+        let mut poly = create_test_poly();
+        let expected = poly.clone();
+        let small = Arc::new(MultiLinearPolynomial::new(1, vec![F::from(1), F::from(2)]));
+
+        assert_eq!(poly.try_add_product(F::from(1), vec![]), Err(PolynomialError::NoPolynomials));
+        assert_eq!(
+            poly.try_add_product(F::from(1), vec![mle([1, 2, 3, 4]), small.clone()]),
+            Err(PolynomialError::VariableMismatch { expected: 2, actual: 1 })
+        );
+        assert_eq!(poly.try_add_mle(small), Err(PolynomialError::VariableMismatch { expected: 2, actual: 1 }));
+        assert_eq!(poly, expected);
+    }
+}
@@ -0,0 +1,32 @@
+use std::fmt;
+
+use polynomials::error::PolynomialError;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShamirError {
+    /// A polynomial of degree `threshold` needs `threshold + 1` shares to be
+    /// reconstructed, so there must be more members than the threshold.
+    NotEnoughMembers { threshold: u64, members: u64 },
+
+    /// The shares could not be interpolated.
+    Polynomial(PolynomialError),
+}
+
+impl fmt::Display for ShamirError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotEnoughMembers { threshold, members } => {
+                write!(f, "{} members cannot reconstruct a secret of threshold {}", members, threshold)
+            }
+            Self::Polynomial(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ShamirError {}
+
+impl From<PolynomialError> for ShamirError {
+    fn from(error: PolynomialError) -> Self {
+        Self::Polynomial(error)
+    }
+}
@@ -1 +1,2 @@
-pub mod shamir_secret_sharing;
\ No newline at end of file
+pub mod shamir_secret_sharing;
+pub mod error;
@@ -1,9 +1,13 @@
 use ark_ff::PrimeField;
-use rand::{rngs, thread_rng};
 use polynomials::univariate_poly::univariat_polynomial;
+use crate::error::ShamirError;
 
 
-pub fn create_shares<F: PrimeField>(seceret: F, threshold: u64, members: u64) ->  (Vec<F>, Vec<F>){
+pub fn create_shares<F: PrimeField>(seceret: F, threshold: u64, members: u64) -> Result<(Vec<F>, Vec<F>), ShamirError> {
+
+    if members <= threshold {
+        return Err(ShamirError::NotEnoughMembers { threshold, members });
+    }
 
     // this is the polynomial created as the seceret
     let mut new_seceret = vec![seceret];
@@ -24,7 +28,7 @@ pub fn create_shares<F: PrimeField>(seceret: F, threshold: u64, members: u64) ->
 
     for i in 0..members {
 
-        let x_coord_values = F::from(i as u64);
+        let x_coord_values = F::from(i);
         x_coord.push(x_coord_values);
 
         let y_coord_values = poly_sec.clone().evaluate(x_coord_values);
@@ -32,20 +36,20 @@ pub fn create_shares<F: PrimeField>(seceret: F, threshold: u64, members: u64) ->
     }
 
 
-    (x_coord, y_coord)
+    Ok((x_coord, y_coord))
 
 
 }
 
 // The parameters would be the x and y coordinates for the secerets 
-pub fn construct_seceret<F: PrimeField>(x_coords_sec: Vec<F>, y_coords_sec: Vec<F>, eval_point: F) -> F {
+pub fn construct_seceret<F: PrimeField>(x_coords_sec: Vec<F>, y_coords_sec: Vec<F>, eval_point: F) -> Result<F, ShamirError> {
 
-    let the_seceret = univariat_polynomial::lagrange_interpolate(&x_coords_sec, &y_coords_sec);
+    let the_seceret = univariat_polynomial::lagrange_interpolate(&x_coords_sec, &y_coords_sec)?;
 
     let evaluation = univariat_polynomial::UnivariantPolynomial::new(the_seceret);
 
     // The secret is constructed back, at this point it can be evaluated at zero to remove all cooefficient remaining the constant 
-    evaluation.evaluate(eval_point)
+    Ok(evaluation.evaluate(eval_point))
 
 }
 
@@ -55,6 +59,7 @@ mod tests {
 
 
     use ark_test_curves::bls12_381::Fr;
+    use polynomials::error::PolynomialError;
 
     use super::*;
 
@@ -69,12 +74,24 @@ mod tests {
         let members = 6;
 
 
-        let the_shares = create_shares(secret, threshold, members);
+        let the_shares = create_shares(secret, threshold, members).unwrap();
 
-        let seceret_construction = construct_seceret(the_shares.0, the_shares.1, Fr::from(0));
+        let seceret_construction = construct_seceret(the_shares.0, the_shares.1, Fr::from(0)).unwrap();
 
         assert_eq!(seceret_construction, F::from(20u64));
     }
 
+    #[test]
+    fn test_secret_sharing_errors() {
+
+        assert_eq!(create_shares(F::from(20), 4, 4), Err(ShamirError::NotEnoughMembers { threshold: 4, members: 4 }));
+
+        let (x_coords, y_coords) = create_shares(F::from(20), 2, 3).unwrap();
+        assert_eq!(
+            construct_seceret(x_coords[..2].to_vec(), y_coords, Fr::from(0)),
+            Err(ShamirError::Polynomial(PolynomialError::CoordinatesMismatch { x_len: 2, y_len: 3 }))
+        );
+    }
+
 
 }
\ No newline at end of file
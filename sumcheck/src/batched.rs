@@ -0,0 +1,248 @@
+use std::sync::Arc;
+
+use ark_ff::PrimeField;
+use polynomials::error::PolynomialError;
+use polynomials::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
+use polynomials::virtual_poly::virtual_polynomial::VirtualPolynomial;
+use crate::composed::composed_sumcheck::{ComposedSumcheck, ComposedSumcheckProof};
+use crate::oracle::DeferredOracle;
+use crate::verifier::SubClaim;
+use crate::error::SumcheckError;
+use fiat_shamir_transcript::transcript_interface::TranscriptInterface;
+
+/// Proves several sumcheck claims `sum_x P_i(x) = s_i` with a single sumcheck of
+/// `sum_x sum_i rho_i P_i(x)` for transcript-derived coefficients `rho_i`.
+/// A polynomial with fewer variables than the largest one is padded with leading
+/// variables it does not depend on, which multiplies its sum by 2 for every extra variable.
+#[derive(Debug, Clone)]
+pub struct BatchedSumcheck<F: PrimeField> {
+    // (polynomial, claimed sum) pairs
+    pub claims: Vec<(VirtualPolynomial<F>, F)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchedSumcheckProof<F: PrimeField> {
+    pub sumcheck_proof: ComposedSumcheckProof<F>,
+    // P_i at its own part of the shared point
+    pub evaluations: Vec<F>,
+}
+
+impl<F: PrimeField> BatchedSumcheck<F> {
+    /// # Panics
+    ///
+    /// If `claims` is empty; see [`Self::try_new`].
+    pub fn new(claims: Vec<(VirtualPolynomial<F>, F)>) -> Self {
+        Self::try_new(claims).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_new(claims: Vec<(VirtualPolynomial<F>, F)>) -> Result<Self, PolynomialError> {
+        if claims.is_empty() {
+            return Err(PolynomialError::NoPolynomials);
+        }
+        Ok(Self { claims })
+    }
+
+    /// Returns the proof and the shared point, of which every polynomial
+    /// is evaluated at the suffix matching its number of variables.
+    pub fn prove<T: TranscriptInterface>(&self, transcript: &mut T) -> (BatchedSumcheckProof<F>, Vec<F>) {
+        let shape: Vec<(usize, F)> = self.claims.iter().map(|(poly, sum)| (poly.num_variables, *sum)).collect();
+        let coefficients = Self::batching_coefficients(&shape, transcript);
+        let num_variables = shape.iter().map(|(n, _)| *n).max().unwrap_or(0);
+
+        let mut combined = VirtualPolynomial::new(num_variables);
+        for ((poly, _), coefficient) in self.claims.iter().zip(&coefficients) {
+            let padded: Vec<Arc<MultiLinearPolynomial<F>>> =
+                poly.mles.iter().map(|mle| Arc::new(pad(mle, num_variables))).collect();
+            for (product_coefficient, indices) in &poly.products {
+                let factors = indices.iter().map(|&index| padded[index].clone()).collect();
+                combined.add_product(*coefficient * product_coefficient, factors);
+            }
+        }
+
+        let sumcheck = ComposedSumcheck {
+            poly: combined,
+            sum: Self::combined_sum(&shape, &coefficients, num_variables),
+        };
+        let (sumcheck_proof, point) = sumcheck.prove(transcript);
+
+        let evaluations: Vec<F> = self
+            .claims
+            .iter()
+            .map(|(poly, _)| poly.evaluate(&point[num_variables - poly.num_variables..]))
+            .collect();
+        transcript.absorb(b"batched_sumcheck_evaluations", evaluations.as_slice());
+
+        (
+            BatchedSumcheckProof {
+                sumcheck_proof,
+                evaluations,
+            },
+            point,
+        )
+    }
+
+    /// Checks a proof for claims given as (number of variables, claimed sum), the polynomials
+    /// having degree at most `max_degree` in every variable. On success, returns for every
+    /// claim the point and the value its polynomial must take there.
+    pub fn verify<T: TranscriptInterface>(
+        proof: &BatchedSumcheckProof<F>,
+        claims: &[(usize, F)],
+        max_degree: usize,
+        transcript: &mut T,
+    ) -> Result<Vec<SubClaim<F>>, SumcheckError<F>> {
+        if proof.evaluations.len() != claims.len() {
+            return Err(SumcheckError::WrongNumberOfEvaluations {
+                expected: claims.len(),
+                actual: proof.evaluations.len(),
+            });
+        }
+
+        let coefficients = Self::batching_coefficients(claims, transcript);
+        let num_variables = claims.iter().map(|(n, _)| *n).max().unwrap_or(0);
+        let sum = Self::combined_sum(claims, &coefficients, num_variables);
+
+        let mut oracle = DeferredOracle::new();
+        ComposedSumcheck::verify_with_oracle(&proof.sumcheck_proof, sum, num_variables, max_degree, &mut oracle, transcript)?;
+
+        let sub_claim = oracle.claims.pop().expect("the deferred oracle records the final claim");
+        let combined_eval: F = coefficients.iter().zip(&proof.evaluations).map(|(c, e)| *c * e).sum();
+        if combined_eval != sub_claim.claimed_eval {
+            return Err(SumcheckError::FinalEvaluationMismatch {
+                expected: sub_claim.claimed_eval,
+                actual: combined_eval,
+            });
+        }
+
+        transcript.absorb(b"batched_sumcheck_evaluations", proof.evaluations.as_slice());
+
+        Ok(
+            claims
+                .iter()
+                .zip(&proof.evaluations)
+                .map(|((n, _), evaluation)| SubClaim {
+                    challenges: sub_claim.challenges[num_variables - n..].to_vec(),
+                    claimed_eval: *evaluation,
+                })
+                .collect(),
+        )
+    }
+
+    // Binds the claims to the transcript before sampling the coefficients
+    fn batching_coefficients<T: TranscriptInterface>(claims: &[(usize, F)], transcript: &mut T) -> Vec<F> {
+        let num_variables: Vec<u8> = claims.iter().flat_map(|(n, _)| (*n as u64).to_le_bytes()).collect();
+        let sums: Vec<F> = claims.iter().map(|(_, sum)| *sum).collect();
+
+        transcript.submit_data(b"batched_sumcheck_num_variables", &num_variables);
+        transcript.absorb(b"batched_sumcheck_claimed_sums", sums.as_slice());
+        transcript.compute_challenges_in_field(b"batched_sumcheck_coefficients", claims.len())
+    }
+
+    fn combined_sum(claims: &[(usize, F)], coefficients: &[F], num_variables: usize) -> F {
+        claims
+            .iter()
+            .zip(coefficients)
+            .map(|((n, sum), coefficient)| *coefficient * sum * F::from(2u64).pow([(num_variables - n) as u64]))
+            .sum()
+    }
+}
+
+// Adds leading variables the polynomial does not depend on, i.e. repeats its table
+fn pad<F: PrimeField>(mle: &MultiLinearPolynomial<F>, num_variables: usize) -> MultiLinearPolynomial<F> {
+    let copies = 1 << (num_variables - mle.variables);
+    MultiLinearPolynomial::new(num_variables, mle.evaluations.repeat(copies))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr as F;
+    use fiat_shamir_transcript::transcript::Transcript;
+
+    fn mle(evaluations: &[u64]) -> Arc<MultiLinearPolynomial<F>> {
+        let variables = evaluations.len().trailing_zeros() as usize;
+        Arc::new(MultiLinearPolynomial::new(variables, evaluations.iter().map(|&e| F::from(e)).collect()))
+    }
+
+    fn create_test_claims() -> Vec<(VirtualPolynomial<F>, F)> {
+        // A * B over 3 variables
+        let mut poly_1 = VirtualPolynomial::new(3);
+        poly_1.add_product(F::from(1), vec![mle(&[1, 2, 3, 4, 5, 6, 7, 8]), mle(&[2, 0, 1, 1, 3, 0, 2, 5])]);
+
+        // 2 * C * C * D over 2 variables
+        let mut poly_2 = VirtualPolynomial::new(2);
+        let c = mle(&[1, 3, 0, 2]);
+        poly_2.add_product(F::from(2), vec![c.clone(), c, mle(&[4, 1, 5, 2])]);
+
+        // E over a single variable
+        let mut poly_3 = VirtualPolynomial::new(1);
+        poly_3.add_product(F::from(1), vec![mle(&[7, 9])]);
+
+        [poly_1, poly_2, poly_3]
+            .into_iter()
+            .map(|poly| {
+                let sum = poly.evaluations().iter().sum();
+                (poly, sum)
+            })
+            .collect()
+    }
+
+    fn shape(claims: &[(VirtualPolynomial<F>, F)]) -> Vec<(usize, F)> {
+        claims.iter().map(|(poly, sum)| (poly.num_variables, *sum)).collect()
+    }
+
+    #[test]
+    fn test_batched_sumcheck() {
+        let claims = create_test_claims();
+        let (proof, point) = BatchedSumcheck::new(claims.clone()).prove(&mut Transcript::new(b"test"));
+
+        let sub_claims = BatchedSumcheck::verify(&proof, &shape(&claims), 3, &mut Transcript::new(b"test")).unwrap();
+
+        assert_eq!(point.len(), 3);
+        for ((poly, _), sub_claim) in claims.iter().zip(&sub_claims) {
+            assert_eq!(sub_claim.challenges, point[3 - poly.num_variables..].to_vec());
+            assert_eq!(sub_claim.claimed_eval, poly.evaluate(&sub_claim.challenges));
+        }
+    }
+
+    #[test]
+    fn test_batched_sumcheck_wrong_sum() {
+        let mut claims = create_test_claims();
+        claims[1].1 += F::from(1);
+        let (proof, _) = BatchedSumcheck::new(claims.clone()).prove(&mut Transcript::new(b"test"));
+
+        assert!(matches!(
+            BatchedSumcheck::verify(&proof, &shape(&claims), 3, &mut Transcript::new(b"test")),
+            Err(SumcheckError::RoundSumMismatch { round: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_batched_sumcheck_wrong_evaluation() {
+        let claims = create_test_claims();
+        let (mut proof, _) = BatchedSumcheck::new(claims.clone()).prove(&mut Transcript::new(b"test"));
+        proof.evaluations[2] += F::from(1);
+
+        assert!(matches!(
+            BatchedSumcheck::verify(&proof, &shape(&claims), 3, &mut Transcript::new(b"test")),
+            Err(SumcheckError::FinalEvaluationMismatch { .. })
+        ));
+        assert_eq!(
+            BatchedSumcheck::verify(&proof, &shape(&claims[..2]), 3, &mut Transcript::new(b"test")),
+            Err(SumcheckError::WrongNumberOfEvaluations { expected: 2, actual: 3 })
+        );
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(matches!(BatchedSumcheck::<F>::try_new(vec![]), Err(PolynomialError::NoPolynomials)));
+        assert!(BatchedSumcheck::try_new(create_test_claims()).is_ok());
+    }
+
+    #[test]
+    fn test_pad() {
+        let padded = pad(&mle(&[7, 9]), 3);
+
+        assert_eq!(padded.evaluations, [7, 9, 7, 9, 7, 9, 7, 9].map(F::from).to_vec());
+        assert_eq!(padded.eval_full(&[F::from(5), F::from(6), F::from(2)]), mle(&[7, 9]).eval_full(&[F::from(2)]));
+    }
+}
@@ -1,46 +1,113 @@
 use ark_ff::PrimeField;
-use polynomials::composed::composed_multilinear::ComposedMultilinearPolynomial;
-use crate::utils::vec_to_bytes;
-use fiat_shamir_transcript::transcript::Transcript;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate, Write};
+use polynomials::virtual_poly::virtual_polynomial::VirtualPolynomial;
+use crate::oracle::EvaluationOracle;
+use crate::utils::{evaluate_round_poly, round_poly_sum};
+use crate::error::{ProofFormatError, SumcheckError};
+use crate::serialization::{proof_from_bytes, proof_to_bytes};
+use fiat_shamir_transcript::absorb::{write_length, Absorb};
+use fiat_shamir_transcript::transcript_interface::TranscriptInterface;
 
+/// Sumcheck over a sum of weighted products of multilinear polynomials, a single
+/// `ComposedMultilinearPolynomial` being the special case of one product.
 #[derive(Debug, Clone)]
 pub struct ComposedSumcheck<F: PrimeField> {
-    pub poly: ComposedMultilinearPolynomial<F>,
+    pub poly: VirtualPolynomial<F>,
     pub sum: F,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ComposedSumcheckProof<F: PrimeField> {
+    // Evaluations of every round polynomial at 0, 1, ..., max_degree
     pub round_polys: Vec<Vec<F>>,
     pub final_eval: F,
 }
 
+impl<F: PrimeField> ComposedSumcheckProof<F> {
+    /// The proof in the versioned byte format of [`crate::serialization`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        proof_to_bytes(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofFormatError> {
+        proof_from_bytes(bytes)
+    }
+}
+
+impl<F: PrimeField> Absorb for ComposedSumcheckProof<F> {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        write_length(self.round_polys.len(), bytes);
+        for round_poly in &self.round_polys {
+            round_poly.as_slice().write_transcript_bytes(bytes);
+        }
+        self.final_eval.write_transcript_bytes(bytes);
+    }
+}
+
+impl<F: PrimeField> CanonicalSerialize for ComposedSumcheckProof<F> {
+    fn serialize_with_mode<W: Write>(&self, mut writer: W, compress: Compress) -> Result<(), SerializationError> {
+        self.round_polys.serialize_with_mode(&mut writer, compress)?;
+        self.final_eval.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.round_polys.serialized_size(compress) + self.final_eval.serialized_size(compress)
+    }
+}
+
+// Every round polynomial has the same degree, and at least the evaluations at 0 and 1
+impl<F: PrimeField> Valid for ComposedSumcheckProof<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        if let Some(first) = self.round_polys.first() {
+            if first.len() < 2 || self.round_polys.iter().any(|round_poly| round_poly.len() != first.len()) {
+                return Err(SerializationError::InvalidData);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for ComposedSumcheckProof<F> {
+    fn deserialize_with_mode<R: Read>(mut reader: R, compress: Compress, validate: Validate) -> Result<Self, SerializationError> {
+        let proof = Self {
+            round_polys: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            final_eval: F::deserialize_with_mode(&mut reader, compress, validate)?,
+        };
+        if let Validate::Yes = validate {
+            proof.check()?;
+        }
+        Ok(proof)
+    }
+}
+
 impl<F: PrimeField> ComposedSumcheck<F> {
-    pub fn new(poly: ComposedMultilinearPolynomial<F>) -> Self {
+    pub fn new(poly: impl Into<VirtualPolynomial<F>>) -> Self {
+        let poly = poly.into();
         let sum = Self::calculate_sum(&poly);
         ComposedSumcheck { poly, sum }
     }
 
-    pub fn calculate_sum(poly: &ComposedMultilinearPolynomial<F>) -> F {
-        poly.elementwise_product().iter().sum()
+    pub fn calculate_sum(poly: &VirtualPolynomial<F>) -> F {
+        poly.evaluations().iter().sum()
     }
 
-    pub fn prove(&self) -> (ComposedSumcheckProof<F>, Vec<F>) {
-        let mut transcript = Transcript::new();
+    pub fn prove<T: TranscriptInterface>(&self, transcript: &mut T) -> (ComposedSumcheckProof<F>, Vec<F>) {
         let mut current_poly = self.poly.clone();
-        let variables = self.poly.polys[0].variables;
+        let variables = self.poly.num_variables;
         let mut round_polys = Vec::with_capacity(variables);
         let mut challenges = Vec::with_capacity(variables);
 
+        transcript.absorb(b"composed_sumcheck_claimed_sum", &self.sum);
+
         for _ in 0..variables {
-            // Calculate round polynomial coefficients
+            // Calculate round polynomial evaluations
             let round_poly = self.compute_round_poly(&current_poly);
             
             // Commit to round polynomial
-            transcript.append(&vec_to_bytes(&round_poly));
+            transcript.absorb(b"composed_sumcheck_round_poly", round_poly.as_slice());
             
             // Generate challenge and update state
-            let challenge: F = transcript.sample_challenge();
+            let challenge: F = transcript.compute_challenge_in_field(b"composed_sumcheck_challenge");
             challenges.push(challenge);
             round_polys.push(round_poly);
             
@@ -49,7 +116,7 @@ impl<F: PrimeField> ComposedSumcheck<F> {
         }
 
         let final_eval = current_poly.evaluate(&[]);
-        
+
         (
             ComposedSumcheckProof {
                 round_polys,
@@ -59,72 +126,110 @@ impl<F: PrimeField> ComposedSumcheck<F> {
         )
     }
 
-    fn compute_round_poly(&self, poly: &ComposedMultilinearPolynomial<F>) -> Vec<F> {
+    fn compute_round_poly(&self, poly: &VirtualPolynomial<F>) -> Vec<F> {
         let max_degree = poly.max_degree();
         let mut round_poly = Vec::with_capacity(max_degree + 1);
-        
+
         for i in 0..=max_degree {
             let eval = poly
                 .partial_eval(F::from(i as u32), 0)
-                .elementwise_product()
+                .evaluations()
                 .iter()
                 .sum();
             round_poly.push(eval);
         }
-        
+
         round_poly
     }
 
-    pub fn verify(&self, proof: &ComposedSumcheckProof<F>, sum: F) -> bool {
-        let mut transcript = Transcript::new();
-        let mut current_sum = self.sum;
+    /// Checks the proof against the polynomial held by `self`.
+    pub fn verify<T: TranscriptInterface>(&self, proof: &ComposedSumcheckProof<F>, sum: F, transcript: &mut T) -> Result<(), SumcheckError<F>> {
+        Self::verify_with_oracle(
+            proof,
+            sum,
+            self.poly.num_variables,
+            self.poly.max_degree(),
+            &mut self.poly.clone(),
+            transcript,
+        )
+    }
+
+    /// Checks a proof over `num_variables` variables whose round polynomials have degree
+    /// at most `max_degree`, leaving the final evaluation check to `oracle`.
+    pub fn verify_with_oracle<O: EvaluationOracle<F>, T: TranscriptInterface>(
+        proof: &ComposedSumcheckProof<F>,
+        sum: F,
+        num_variables: usize,
+        max_degree: usize,
+        oracle: &mut O,
+        transcript: &mut T,
+    ) -> Result<(), SumcheckError<F>> {
+        if proof.round_polys.len() != num_variables {
+            return Err(SumcheckError::WrongNumberOfRounds {
+                expected: num_variables,
+                actual: proof.round_polys.len(),
+            });
+        }
+
+        let mut current_sum = sum;
         let mut challenges = Vec::new();
 
+        transcript.absorb(b"composed_sumcheck_claimed_sum", &sum);
+
         // Verify each round
-        for round_poly in &proof.round_polys {
+        for (i, round_poly) in proof.round_polys.iter().enumerate() {
+            // A degree d polynomial is sent as its d + 1 evaluations, anything longer could exceed the bound
+            if round_poly.len() != max_degree + 1 {
+                return Err(SumcheckError::RoundPolynomialLength {
+                    round: i,
+                    expected: max_degree + 1,
+                    actual: round_poly.len(),
+                });
+            }
+
             // Verify claimed sum matches round polynomial evaluation
-            if !self.verify_round_consistency(round_poly, current_sum) {
-                return false;
+            if round_poly_sum(round_poly) != current_sum {
+                return Err(SumcheckError::RoundSumMismatch {
+                    round: i,
+                    expected: current_sum,
+                    actual: round_poly_sum(round_poly),
+                });
             }
 
             // Generate and store challenge
-            transcript.append(&vec_to_bytes(round_poly));
-            let challenge: F = transcript.sample_challenge();
+            transcript.absorb(b"composed_sumcheck_round_poly", round_poly.as_slice());
+            let challenge: F = transcript.compute_challenge_in_field(b"composed_sumcheck_challenge");
             challenges.push(challenge);
 
             // Update current sum for next round
-            current_sum = self.evaluate_uni_poly(round_poly, challenge);
+            current_sum = evaluate_round_poly(round_poly, challenge);
         }
 
         // Final verification
-        self.poly.evaluate(&challenges) == proof.final_eval
-    }
-
-    fn verify_round_consistency(&self, round_poly: &[F], claimed_sum: F) -> bool {
-        let eval_at_zero = round_poly[0];
-        let eval_at_one = self.evaluate_uni_poly(round_poly, F::one());
-        eval_at_zero + eval_at_one == claimed_sum
-    }
-
-    fn evaluate_uni_poly(&self, coeffs: &[F], point: F) -> F {
-        let mut result = F::zero();
-        let mut power = F::one();
-        
-        for &coeff in coeffs {
-            result += coeff * power;
-            power *= point;
+        if current_sum != proof.final_eval {
+            return Err(SumcheckError::FinalEvaluationMismatch {
+                expected: current_sum,
+                actual: proof.final_eval,
+            });
         }
-        
-        result
+        if !oracle.check_evaluation(&challenges, proof.final_eval) {
+            return Err(SumcheckError::OracleRejected {
+                claimed_eval: proof.final_eval,
+            });
+        }
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ark_ff::Field;
+    use fiat_shamir_transcript::transcript::Transcript;
     use ark_test_curves::bls12_381::Fr as F;
+    use polynomials::composed::composed_multilinear::ComposedMultilinearPolynomial;
     use polynomials::multilinear_pol::multilinear_poly::*;
+    use std::sync::Arc;
     
    
 
@@ -154,8 +259,117 @@ mod tests {
         let composed_poly =  ComposedMultilinearPolynomial::new(vec![poly1, poly2]);
 
         let sumcheck =  ComposedSumcheck::new(composed_poly);
-        let (proof, _challenges) = &sumcheck.prove();
-        let verifer: bool = sumcheck.verify(&proof, sumcheck.sum);
-        assert_eq!(verifer, true);
+        let (proof, _challenges) = &sumcheck.prove(&mut Transcript::new(b"test"));
+        assert!(sumcheck.verify(proof, sumcheck.sum, &mut Transcript::new(b"test")).is_ok());
+    }
+
+    fn create_test_poly_product() -> ComposedMultilinearPolynomial<F> {
+        let poly1 = MultiLinearPolynomial::new(3, (1..=8).map(F::from).collect());
+        let poly2 = MultiLinearPolynomial::new(3, vec![F::from(2), F::from(0), F::from(5), F::from(1), F::from(3), F::from(3), F::from(0), F::from(7)]);
+        let poly3 = MultiLinearPolynomial::new(3, vec![F::from(1), F::from(4), F::from(1), F::from(0), F::from(6), F::from(2), F::from(9), F::from(1)]);
+        let poly4 = MultiLinearPolynomial::new(3, vec![F::from(5), F::from(1), F::from(2), F::from(2), F::from(1), F::from(0), F::from(3), F::from(4)]);
+        ComposedMultilinearPolynomial::new(vec![poly1, poly2, poly3, poly4])
+    }
+
+    #[test]
+    fn test_sum_check_proof_higher_degree() {
+        for num_polys in [3, 4] {
+            let mut composed_poly = create_test_poly_product();
+            composed_poly.polys.truncate(num_polys);
+
+            let sumcheck = ComposedSumcheck::new(composed_poly.clone());
+            let (proof, challenges) = sumcheck.prove(&mut Transcript::new(b"test"));
+
+            assert!(proof.round_polys.iter().all(|round_poly| round_poly.len() == num_polys + 1));
+            assert_eq!(proof.final_eval, composed_poly.evaluate(&challenges));
+            assert!(sumcheck.verify(&proof, sumcheck.sum, &mut Transcript::new(b"test")).is_ok());
+            assert_eq!(
+                sumcheck.verify(&proof, sumcheck.sum + F::from(1), &mut Transcript::new(b"test")),
+                Err(SumcheckError::RoundSumMismatch {
+                    round: 0,
+                    expected: sumcheck.sum + F::from(1),
+                    actual: sumcheck.sum,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_polynomial_matches_partial_sums() {
+        let composed_poly = create_test_poly_product();
+        let sumcheck = ComposedSumcheck::new(composed_poly.clone());
+        let (proof, challenges) = sumcheck.prove(&mut Transcript::new(b"test"));
+
+        // The first round polynomial at the challenge is the sum over the remaining variables
+        let fixed = composed_poly.partial_eval(challenges[0], 0);
+        assert_eq!(evaluate_round_poly(&proof.round_polys[0], challenges[0]), fixed.elementwise_product().iter().sum());
+    }
+
+    #[test]
+    fn test_degree_bound_is_enforced() {
+        let sumcheck = ComposedSumcheck::new(create_test_poly_product());
+        let (mut proof, _) = sumcheck.prove(&mut Transcript::new(b"test"));
+
+        // An extra evaluation would let the prover send a polynomial of degree 5
+        proof.round_polys[0].push(F::from(0));
+        assert_eq!(
+            sumcheck.verify(&proof, sumcheck.sum, &mut Transcript::new(b"test")),
+            Err(SumcheckError::RoundPolynomialLength { round: 0, expected: 5, actual: 6 })
+        );
+
+        let (mut proof, _) = sumcheck.prove(&mut Transcript::new(b"test"));
+        proof.round_polys[1].pop();
+        assert_eq!(
+            sumcheck.verify(&proof, sumcheck.sum, &mut Transcript::new(b"test")),
+            Err(SumcheckError::RoundPolynomialLength { round: 1, expected: 5, actual: 4 })
+        );
+    }
+
+    #[test]
+    fn test_tampered_round_polynomial() {
+        let sumcheck = ComposedSumcheck::new(create_test_poly_product());
+        let (mut proof, _) = sumcheck.prove(&mut Transcript::new(b"test"));
+
+        // Still consistent with the claimed sum, but not the honest polynomial
+        proof.round_polys[0][0] += F::from(1);
+        proof.round_polys[0][1] -= F::from(1);
+        assert!(matches!(
+            sumcheck.verify(&proof, sumcheck.sum, &mut Transcript::new(b"test")),
+            Err(SumcheckError::RoundSumMismatch { round: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_sum_check_proof_virtual_polynomial() {
+        // 3 * A * B * E + 5 * C * E, with E shared by both products
+        let polys: Vec<_> = create_test_poly_product().polys.into_iter().map(Arc::new).collect();
+        let mut poly = VirtualPolynomial::new(3);
+        poly.add_product(F::from(3), vec![polys[0].clone(), polys[1].clone(), polys[3].clone()]);
+        poly.add_product(F::from(5), vec![polys[2].clone(), polys[3].clone()]);
+
+        let sumcheck = ComposedSumcheck::new(poly.clone());
+        assert_eq!(sumcheck.sum, poly.evaluations().iter().sum());
+
+        let (proof, challenges) = sumcheck.prove(&mut Transcript::new(b"test"));
+        assert!(proof.round_polys.iter().all(|round_poly| round_poly.len() == 4));
+        assert_eq!(proof.final_eval, poly.evaluate(&challenges));
+        assert!(sumcheck.verify(&proof, sumcheck.sum, &mut Transcript::new(b"test")).is_ok());
+        assert!(sumcheck.verify(&proof, sumcheck.sum - F::from(1), &mut Transcript::new(b"test")).is_err());
+    }
+
+    #[test]
+    fn test_sum_check_proof_without_products() {
+        // The zero polynomial has degree 0, so every round is a single evaluation
+        let sumcheck = ComposedSumcheck::new(VirtualPolynomial::new(2));
+        let (mut proof, _) = sumcheck.prove(&mut Transcript::new(b"test"));
+
+        assert_eq!(proof.round_polys, vec![vec![F::from(0)]; 2]);
+        assert!(sumcheck.verify(&proof, sumcheck.sum, &mut Transcript::new(b"test")).is_ok());
+
+        proof.round_polys[0][0] = F::from(1);
+        assert!(matches!(
+            sumcheck.verify(&proof, sumcheck.sum, &mut Transcript::new(b"test")),
+            Err(SumcheckError::RoundSumMismatch { round: 0, actual, .. }) if actual == F::from(2)
+        ));
     }
 }
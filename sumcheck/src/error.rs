@@ -0,0 +1,93 @@
+use std::fmt;
+
+use ark_ff::PrimeField;
+use ark_serialize::SerializationError;
+
+/// Why a verifier rejected a proof. Rounds and layers are counted from zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SumcheckError<F: PrimeField> {
+    /// The proof does not have one round polynomial per variable.
+    WrongNumberOfRounds { expected: usize, actual: usize },
+
+    /// A round polynomial is not sent as the expected number of evaluations,
+    /// i.e. it does not have the expected degree.
+    RoundPolynomialLength { round: usize, expected: usize, actual: usize },
+
+    /// `p(0) + p(1)` of a round polynomial is not the claim of the previous round.
+    RoundSumMismatch { round: usize, expected: F, actual: F },
+
+    /// The value the rounds end on is not the one claimed by the prover.
+    FinalEvaluationMismatch { expected: F, actual: F },
+
+    /// The evaluation oracle rejected the final claim.
+    OracleRejected { claimed_eval: F },
+
+    /// The proof does not carry one evaluation per claim.
+    WrongNumberOfEvaluations { expected: usize, actual: usize },
+
+    /// `eq(x, r)` vanishes at the sumcheck point, so the zerocheck claim cannot be recovered.
+    EqVanishes,
+
+    /// The check of a layer of a layered protocol failed.
+    Layer { layer: usize, error: Box<SumcheckError<F>> },
+}
+
+impl<F: PrimeField> fmt::Display for SumcheckError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongNumberOfRounds { expected, actual } => {
+                write!(f, "expected {} round polynomials, got {}", expected, actual)
+            }
+            Self::RoundPolynomialLength { round, expected, actual } => {
+                write!(f, "round polynomial {} has {} evaluations instead of {}", round, actual, expected)
+            }
+            Self::RoundSumMismatch { round, expected, actual } => {
+                write!(f, "round polynomial sum mismatch at round {}: {} != {}", round, actual, expected)
+            }
+            Self::FinalEvaluationMismatch { expected, actual } => {
+                write!(f, "final evaluation mismatch: {} != {}", actual, expected)
+            }
+            Self::OracleRejected { claimed_eval } => {
+                write!(f, "the oracle rejected the final evaluation {}", claimed_eval)
+            }
+            Self::WrongNumberOfEvaluations { expected, actual } => {
+                write!(f, "expected {} evaluations, got {}", expected, actual)
+            }
+            Self::EqVanishes => write!(f, "eq(x, r) vanishes at the sumcheck point"),
+            Self::Layer { layer, error } => write!(f, "layer {}: {}", layer, error),
+        }
+    }
+}
+
+impl<F: PrimeField> std::error::Error for SumcheckError<F> {}
+
+/// Why a proof could not be read back from the versioned byte format.
+#[derive(Debug)]
+pub enum ProofFormatError {
+    /// The bytes were written in a format version this build cannot read.
+    UnsupportedVersion(u8),
+
+    /// Bytes are left over after the proof.
+    TrailingBytes(usize),
+
+    /// The proof itself is truncated or malformed.
+    Serialization(SerializationError),
+}
+
+impl fmt::Display for ProofFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => write!(f, "unsupported proof format version {}", version),
+            Self::TrailingBytes(len) => write!(f, "{} trailing bytes after the proof", len),
+            Self::Serialization(error) => write!(f, "malformed proof: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ProofFormatError {}
+
+impl From<SerializationError> for ProofFormatError {
+    fn from(error: SerializationError) -> Self {
+        Self::Serialization(error)
+    }
+}
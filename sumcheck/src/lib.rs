@@ -1,4 +1,11 @@
 pub mod utils;
+pub mod error;
+pub mod serialization;
 pub mod prover;
 pub mod verifier;
-pub mod composed; 
\ No newline at end of file
+pub mod oracle;
+pub mod composed;
+pub mod multi_composed;
+pub mod zerocheck;
+pub mod product_check;
+pub mod batched;
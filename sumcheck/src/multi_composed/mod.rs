@@ -0,0 +1 @@
+pub mod multi_composed_sumcheck;
\ No newline at end of file
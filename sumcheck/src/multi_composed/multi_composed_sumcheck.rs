@@ -0,0 +1,253 @@
+use ark_ff::PrimeField;
+use polynomials::composed::composed_multilinear::ComposedMultilinearPolynomial;
+use polynomials::error::PolynomialError;
+use crate::utils::{evaluate_round_poly, round_poly_sum};
+use crate::verifier::SubClaim;
+use crate::error::SumcheckError;
+use fiat_shamir_transcript::absorb::{write_length, Absorb};
+use fiat_shamir_transcript::transcript_interface::TranscriptInterface;
+
+/// Sumcheck over a sum of products of multilinear polynomials,
+/// i.e. the claim is `sum_x (P_0(x) + P_1(x) + ...)` where every `P_j` is a `ComposedMultilinearPolynomial`.
+#[derive(Debug, Clone)]
+pub struct MultiComposedSumcheck<F: PrimeField> {
+    pub polys: Vec<ComposedMultilinearPolynomial<F>>,
+    pub sum: F,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiComposedSumcheckProof<F: PrimeField> {
+    // Evaluations of every round polynomial at 0, 1, ..., max_degree
+    pub round_polys: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> Absorb for MultiComposedSumcheckProof<F> {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        write_length(self.round_polys.len(), bytes);
+        for round_poly in &self.round_polys {
+            round_poly.as_slice().write_transcript_bytes(bytes);
+        }
+    }
+}
+
+impl<F: PrimeField> MultiComposedSumcheck<F> {
+    /// # Panics
+    ///
+    /// If `polys` is empty or the polynomials differ in their number of variables;
+    /// see [`Self::try_new`].
+    pub fn new(polys: Vec<ComposedMultilinearPolynomial<F>>) -> Self {
+        Self::try_new(polys).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_new(polys: Vec<ComposedMultilinearPolynomial<F>>) -> Result<Self, PolynomialError> {
+        let sum = Self::calculate_sum(&polys);
+        Self::try_new_with_sum(polys, sum)
+    }
+
+    /// # Panics
+    ///
+    /// If `polys` is empty or the polynomials differ in their number of variables;
+    /// see [`Self::try_new_with_sum`].
+    pub fn new_with_sum(polys: Vec<ComposedMultilinearPolynomial<F>>, sum: F) -> Self {
+        Self::try_new_with_sum(polys, sum).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_new_with_sum(polys: Vec<ComposedMultilinearPolynomial<F>>, sum: F) -> Result<Self, PolynomialError> {
+        // `polys` is public, so a composed polynomial may have no factors
+        let variables = |p: &ComposedMultilinearPolynomial<F>| {
+            p.polys.first().map(|mle| mle.variables).ok_or(PolynomialError::NoPolynomials)
+        };
+        let expected = variables(polys.first().ok_or(PolynomialError::NoPolynomials)?)?;
+        for poly in &polys {
+            let actual = variables(poly)?;
+            if actual != expected {
+                return Err(PolynomialError::VariableMismatch { expected, actual });
+            }
+        }
+        Ok(Self { polys, sum })
+    }
+
+    pub fn calculate_sum(polys: &[ComposedMultilinearPolynomial<F>]) -> F {
+        polys.iter().map(|p| p.elementwise_product().iter().sum::<F>()).sum()
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.polys[0].polys[0].variables
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.polys.iter().map(|p| p.max_degree()).max().unwrap_or(0)
+    }
+
+    pub fn prove<T: TranscriptInterface>(&self, transcript: &mut T) -> (MultiComposedSumcheckProof<F>, Vec<F>) {
+        let variables = self.num_variables();
+        let degree = self.max_degree();
+        let mut current_polys = self.polys.clone();
+        let mut round_polys = Vec::with_capacity(variables);
+        let mut challenges = Vec::with_capacity(variables);
+
+        transcript.absorb(b"multi_composed_sumcheck_claimed_sum", &self.sum);
+
+        for _ in 0..variables {
+            let round_poly = Self::compute_round_poly(&current_polys, degree);
+            transcript.absorb(b"multi_composed_sumcheck_round_poly", round_poly.as_slice());
+
+            let challenge: F = transcript.compute_challenge_in_field(b"multi_composed_sumcheck_challenge");
+            challenges.push(challenge);
+            round_polys.push(round_poly);
+
+            current_polys = current_polys.iter().map(|p| p.partial_eval(challenge, 0)).collect();
+        }
+
+        (MultiComposedSumcheckProof { round_polys }, challenges)
+    }
+
+    /// Runs the round checks only and returns the claim about the summed polynomial
+    /// at the random point, which the caller has to check on its own.
+    pub fn verify<T: TranscriptInterface>(
+        proof: &MultiComposedSumcheckProof<F>,
+        sum: F,
+        variables: usize,
+        max_degree: usize,
+        transcript: &mut T,
+    ) -> Result<SubClaim<F>, SumcheckError<F>> {
+        if proof.round_polys.len() != variables {
+            return Err(SumcheckError::WrongNumberOfRounds {
+                expected: variables,
+                actual: proof.round_polys.len(),
+            });
+        }
+
+        let mut current_sum = sum;
+        let mut challenges = Vec::with_capacity(variables);
+
+        transcript.absorb(b"multi_composed_sumcheck_claimed_sum", &sum);
+
+        for (i, round_poly) in proof.round_polys.iter().enumerate() {
+            if round_poly.len() != max_degree + 1 {
+                return Err(SumcheckError::RoundPolynomialLength {
+                    round: i,
+                    expected: max_degree + 1,
+                    actual: round_poly.len(),
+                });
+            }
+
+            if round_poly_sum(round_poly) != current_sum {
+                return Err(SumcheckError::RoundSumMismatch {
+                    round: i,
+                    expected: current_sum,
+                    actual: round_poly_sum(round_poly),
+                });
+            }
+
+            transcript.absorb(b"multi_composed_sumcheck_round_poly", round_poly.as_slice());
+            let challenge: F = transcript.compute_challenge_in_field(b"multi_composed_sumcheck_challenge");
+            challenges.push(challenge);
+
+            current_sum = evaluate_round_poly(round_poly, challenge);
+        }
+
+        Ok(SubClaim {
+            challenges,
+            claimed_eval: current_sum,
+        })
+    }
+
+    fn compute_round_poly(polys: &[ComposedMultilinearPolynomial<F>], degree: usize) -> Vec<F> {
+        (0..=degree)
+            .map(|i| {
+                polys
+                    .iter()
+                    .map(|p| p.partial_eval(F::from(i as u64), 0).elementwise_product().iter().sum::<F>())
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr as F;
+    use fiat_shamir_transcript::transcript::Transcript;
+    use polynomials::multilinear_pol::multilinear_poly::*;
+
+    fn create_test_polys() -> Vec<ComposedMultilinearPolynomial<F>> {
+        let poly1 = MultiLinearPolynomial::new(2, vec![F::from(0), F::from(1), F::from(2), F::from(3)]);
+        let poly2 = MultiLinearPolynomial::new(2, vec![F::from(0), F::from(0), F::from(0), F::from(1)]);
+        let poly3 = MultiLinearPolynomial::new(2, vec![F::from(4), F::from(1), F::from(7), F::from(2)]);
+
+        vec![
+            ComposedMultilinearPolynomial::new(vec![poly1.clone(), poly2.clone(), poly3.clone()]),
+            ComposedMultilinearPolynomial::new(vec![poly1, poly3]),
+            ComposedMultilinearPolynomial::new(vec![poly2]),
+        ]
+    }
+
+    #[test]
+    fn test_calculate_sum() {
+        // (3 * 1 * 2) + (0 * 4 + 1 * 1 + 2 * 7 + 3 * 2) + 1
+        assert_eq!(MultiComposedSumcheck::new(create_test_polys()).sum, F::from(28));
+    }
+
+    #[test]
+    fn test_try_new() {
+        let small = MultiLinearPolynomial::new(1, vec![F::from(1), F::from(2)]);
+        let mut polys = create_test_polys();
+        polys.push(ComposedMultilinearPolynomial::new(vec![small]));
+
+        assert!(matches!(MultiComposedSumcheck::<F>::try_new(vec![]), Err(PolynomialError::NoPolynomials)));
+        assert!(matches!(
+            MultiComposedSumcheck::try_new_with_sum(polys, F::from(0)),
+            Err(PolynomialError::VariableMismatch { expected: 2, actual: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_sum_check_proof() {
+        let polys = create_test_polys();
+        let sumcheck = MultiComposedSumcheck::new(polys.clone());
+        let (proof, challenges) = sumcheck.prove(&mut Transcript::new(b"test"));
+
+        let sub_claim = MultiComposedSumcheck::verify(
+            &proof,
+            sumcheck.sum,
+            sumcheck.num_variables(),
+            sumcheck.max_degree(),
+            &mut Transcript::new(b"test"),
+        )
+        .unwrap();
+
+        assert_eq!(sub_claim.challenges, challenges);
+        let expected: F = polys.iter().map(|p| p.evaluate(&challenges)).sum();
+        assert_eq!(sub_claim.claimed_eval, expected);
+    }
+
+    #[test]
+    fn test_sum_check_proof_wrong_sum() {
+        let sumcheck = MultiComposedSumcheck::new_with_sum(create_test_polys(), F::from(27));
+        let (proof, _) = sumcheck.prove(&mut Transcript::new(b"test"));
+
+        let sub_claim = MultiComposedSumcheck::verify(
+            &proof,
+            sumcheck.sum,
+            sumcheck.num_variables(),
+            sumcheck.max_degree(),
+            &mut Transcript::new(b"test"),
+        );
+        assert!(matches!(sub_claim, Err(SumcheckError::RoundSumMismatch { round: 0, .. })));
+    }
+
+    #[test]
+    fn test_verify_degree_zero_rounds() {
+        // A constant 3 over one variable sums to 6
+        let proof = MultiComposedSumcheckProof { round_polys: vec![vec![F::from(3)]] };
+
+        let sub_claim = MultiComposedSumcheck::verify(&proof, F::from(6), 1, 0, &mut Transcript::new(b"test")).unwrap();
+        assert_eq!(sub_claim.claimed_eval, F::from(3));
+        assert!(matches!(
+            MultiComposedSumcheck::verify(&proof, F::from(3), 1, 0, &mut Transcript::new(b"test")),
+            Err(SumcheckError::RoundSumMismatch { round: 0, .. })
+        ));
+    }
+}
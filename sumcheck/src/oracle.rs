@@ -0,0 +1,134 @@
+use ark_ff::PrimeField;
+use polynomials::composed::composed_multilinear::ComposedMultilinearPolynomial;
+use polynomials::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
+use polynomials::virtual_poly::virtual_polynomial::VirtualPolynomial;
+use crate::verifier::SubClaim;
+
+/// The final check of a sumcheck: the verifier asks the oracle whether the summed
+/// polynomial takes the value `claimed_eval` at the random `point`.
+pub trait EvaluationOracle<F: PrimeField> {
+    fn check_evaluation(&mut self, point: &[F], claimed_eval: F) -> bool;
+}
+
+// The verifier holds the polynomial and evaluates it itself
+impl<F: PrimeField> EvaluationOracle<F> for MultiLinearPolynomial<F> {
+    fn check_evaluation(&mut self, point: &[F], claimed_eval: F) -> bool {
+        self.eval_full(point) == claimed_eval
+    }
+}
+
+impl<F: PrimeField> EvaluationOracle<F> for ComposedMultilinearPolynomial<F> {
+    fn check_evaluation(&mut self, point: &[F], claimed_eval: F) -> bool {
+        self.evaluate(point) == claimed_eval
+    }
+}
+
+impl<F: PrimeField> EvaluationOracle<F> for VirtualPolynomial<F> {
+    fn check_evaluation(&mut self, point: &[F], claimed_eval: F) -> bool {
+        self.evaluate(point) == claimed_eval
+    }
+}
+
+/// Checks the claim against an opening of a polynomial commitment, `verify_opening`
+/// being the opening check of the commitment scheme in use.
+#[derive(Clone, Debug)]
+pub struct CommitmentOracle<C, O, V> {
+    pub commitment: C,
+    pub opening: O,
+    pub verify_opening: V,
+}
+
+impl<C, O, V> CommitmentOracle<C, O, V> {
+    pub fn new(commitment: C, opening: O, verify_opening: V) -> Self {
+        Self {
+            commitment,
+            opening,
+            verify_opening,
+        }
+    }
+}
+
+impl<F, C, O, V> EvaluationOracle<F> for CommitmentOracle<C, O, V>
+where
+    F: PrimeField,
+    V: Fn(&C, &[F], F, &O) -> bool,
+{
+    fn check_evaluation(&mut self, point: &[F], claimed_eval: F) -> bool {
+        (self.verify_opening)(&self.commitment, point, claimed_eval, &self.opening)
+    }
+}
+
+/// Accepts every claim and records it, leaving the caller to check it later
+/// (e.g. as the next claim of a larger protocol).
+#[derive(Clone, Debug, Default)]
+pub struct DeferredOracle<F: PrimeField> {
+    pub claims: Vec<SubClaim<F>>,
+}
+
+impl<F: PrimeField> DeferredOracle<F> {
+    pub fn new() -> Self {
+        Self { claims: Vec::new() }
+    }
+}
+
+impl<F: PrimeField> EvaluationOracle<F> for DeferredOracle<F> {
+    fn check_evaluation(&mut self, point: &[F], claimed_eval: F) -> bool {
+        self.claims.push(SubClaim {
+            challenges: point.to_vec(),
+            claimed_eval,
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr;
+
+    type F = Fr;
+
+    fn create_test_poly() -> MultiLinearPolynomial<F> {
+        MultiLinearPolynomial::new(2, vec![F::from(0), F::from(0), F::from(2), F::from(5)])
+    }
+
+    #[test]
+    fn test_polynomial_oracle() {
+        let mut poly = create_test_poly();
+        let point = vec![F::from(3), F::from(5)];
+
+        assert!(poly.check_evaluation(&point, F::from(51)));
+        assert!(!poly.check_evaluation(&point, F::from(50)));
+    }
+
+    #[test]
+    fn test_commitment_oracle() {
+        // A trivial scheme where the commitment is the polynomial and the opening is the value
+        let mut oracle = CommitmentOracle::new(
+            create_test_poly(),
+            F::from(51),
+            |commitment: &MultiLinearPolynomial<F>, point: &[F], value: F, opening: &F| {
+                *opening == value && commitment.eval_full(point) == value
+            },
+        );
+        let point = vec![F::from(3), F::from(5)];
+
+        assert!(oracle.check_evaluation(&point, F::from(51)));
+        assert!(!oracle.check_evaluation(&point, F::from(50)));
+    }
+
+    #[test]
+    fn test_deferred_oracle() {
+        let mut oracle = DeferredOracle::new();
+        let point = vec![F::from(3), F::from(5)];
+
+        assert!(oracle.check_evaluation(&point, F::from(50)));
+        assert_eq!(
+            oracle.claims,
+            vec![SubClaim {
+                challenges: point,
+                claimed_eval: F::from(50)
+            }]
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use ark_ff::PrimeField;
+use polynomials::multilinear_pol::eq_poly::{eq_eval, eq_poly};
+use polynomials::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
+use polynomials::virtual_poly::virtual_polynomial::VirtualPolynomial;
+use crate::composed::composed_sumcheck::{ComposedSumcheck, ComposedSumcheckProof};
+use crate::oracle::DeferredOracle;
+use crate::verifier::SubClaim;
+use crate::error::SumcheckError;
+use fiat_shamir_transcript::transcript_interface::TranscriptInterface;
+
+/// Proves that the product of the evaluations of `poly` over the boolean hypercube equals a
+/// claimed value, with the binary tree of layered products: layer k holds 2^k values and
+/// $L_k(x) = L_{k+1}(0, x) \cdot L_{k+1}(1, x)$, the root being the product and the leaves `poly`.
+/// Every layer is reduced to the next one with a degree 3 sumcheck of
+/// $\sum_x \text{eq}(x, r) L_{k+1}(0, x) L_{k+1}(1, x)$.
+#[derive(Debug, Clone)]
+pub struct ProductCheck<F: PrimeField> {
+    pub poly: MultiLinearPolynomial<F>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProductCheckProof<F: PrimeField> {
+    // One sumcheck per layer, from the root down
+    pub layer_proofs: Vec<ComposedSumcheckProof<F>>,
+    // L_{k+1}(0, point) and L_{k+1}(1, point) at the end of every layer sumcheck
+    pub layer_evaluations: Vec<(F, F)>,
+}
+
+impl<F: PrimeField> ProductCheck<F> {
+    pub fn new(poly: MultiLinearPolynomial<F>) -> Self {
+        Self { poly }
+    }
+
+    pub fn product(&self) -> F {
+        self.poly.evaluations.iter().product()
+    }
+
+    /// The layers of the product tree, from the root down to the evaluations of `poly`.
+    pub fn layers(&self) -> Vec<Vec<F>> {
+        let mut layers = vec![self.poly.evaluations.clone()];
+        while layers[0].len() > 1 {
+            let (left, right) = layers[0].split_at(layers[0].len() / 2);
+            let layer = left.iter().zip(right).map(|(l, r)| *l * r).collect();
+            layers.insert(0, layer);
+        }
+        layers
+    }
+
+    /// Returns the proof and the point at which `poly` is left to be evaluated.
+    pub fn prove<T: TranscriptInterface>(&self, transcript: &mut T) -> (ProductCheckProof<F>, Vec<F>) {
+        let layers = self.layers();
+        let num_variables = self.poly.variables;
+        let mut layer_proofs = Vec::with_capacity(num_variables);
+        let mut layer_evaluations = Vec::with_capacity(num_variables);
+
+        transcript.absorb(b"product_check_product", &layers[0][0]);
+        let mut point = vec![];
+
+        for (k, next_layer) in layers.iter().skip(1).enumerate() {
+            let (left, right) = next_layer.split_at(next_layer.len() / 2);
+            let left = Arc::new(MultiLinearPolynomial::new(k, left.to_vec()));
+            let right = Arc::new(MultiLinearPolynomial::new(k, right.to_vec()));
+
+            // The claim on layer k is its MLE at the current point, sum_x eq(x, point) L_k(x)
+            let eq = Arc::new(eq_poly(&point));
+            let sum = layers[k].iter().zip(&eq.evaluations).map(|(v, e)| *v * e).sum();
+
+            let mut poly = VirtualPolynomial::new(k);
+            poly.add_product(F::one(), vec![eq, left.clone(), right.clone()]);
+            let sumcheck = ComposedSumcheck { poly, sum };
+            let (layer_proof, challenges) = sumcheck.prove(transcript);
+
+            let evaluations = (left.eval_full(&challenges), right.eval_full(&challenges));
+            transcript.absorb(b"product_check_layer_evaluations", [evaluations.0, evaluations.1].as_slice());
+            let mu: F = transcript.compute_challenge_in_field(b"product_check_mu");
+
+            layer_proofs.push(layer_proof);
+            layer_evaluations.push(evaluations);
+            point = [vec![mu], challenges].concat();
+        }
+
+        (
+            ProductCheckProof {
+                layer_proofs,
+                layer_evaluations,
+            },
+            point,
+        )
+    }
+
+    /// Checks that the evaluations of a polynomial over `num_variables` variables multiply to
+    /// `product`. On success, returns the point and the value the polynomial must take there,
+    /// which the caller still has to check (e.g. against a commitment).
+    pub fn verify<T: TranscriptInterface>(
+        proof: &ProductCheckProof<F>,
+        product: F,
+        num_variables: usize,
+        transcript: &mut T,
+    ) -> Result<SubClaim<F>, SumcheckError<F>> {
+        if proof.layer_proofs.len() != num_variables {
+            return Err(SumcheckError::WrongNumberOfRounds {
+                expected: num_variables,
+                actual: proof.layer_proofs.len(),
+            });
+        }
+        if proof.layer_evaluations.len() != num_variables {
+            return Err(SumcheckError::WrongNumberOfEvaluations {
+                expected: num_variables,
+                actual: proof.layer_evaluations.len(),
+            });
+        }
+
+        transcript.absorb(b"product_check_product", &product);
+        let mut point = vec![];
+        let mut claim = product;
+
+        for (k, (layer_proof, &(left, right))) in proof.layer_proofs.iter().zip(&proof.layer_evaluations).enumerate() {
+            let layer_error = |error| SumcheckError::Layer {
+                layer: k,
+                error: Box::new(error),
+            };
+
+            let mut oracle = DeferredOracle::new();
+            ComposedSumcheck::verify_with_oracle(layer_proof, claim, k, 3, &mut oracle, transcript).map_err(layer_error)?;
+
+            let sub_claim = oracle.claims.pop().expect("the deferred oracle records the final claim");
+            let expected = eq_eval(&sub_claim.challenges, &point) * left * right;
+            if sub_claim.claimed_eval != expected {
+                return Err(layer_error(SumcheckError::FinalEvaluationMismatch {
+                    expected,
+                    actual: sub_claim.claimed_eval,
+                }));
+            }
+
+            transcript.absorb(b"product_check_layer_evaluations", [left, right].as_slice());
+            let mu: F = transcript.compute_challenge_in_field(b"product_check_mu");
+
+            // The line through L(0, point) and L(1, point), taken at mu
+            claim = left + mu * (right - left);
+            point = [vec![mu], sub_claim.challenges].concat();
+        }
+
+        Ok(SubClaim {
+            challenges: point,
+            claimed_eval: claim,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr as F;
+    use fiat_shamir_transcript::transcript::Transcript;
+
+    fn create_test_poly() -> MultiLinearPolynomial<F> {
+        MultiLinearPolynomial::new(3, (1..=8).map(F::from).collect())
+    }
+
+    #[test]
+    fn test_layers() {
+        let layers = ProductCheck::new(create_test_poly()).layers();
+
+        assert_eq!(layers.len(), 4);
+        assert_eq!(layers[0], vec![F::from(40320)]);
+        assert_eq!(layers[2], vec![F::from(5), F::from(12), F::from(21), F::from(32)]);
+    }
+
+    #[test]
+    fn test_product_check() {
+        let poly = create_test_poly();
+        let product_check = ProductCheck::new(poly.clone());
+        let (proof, point) = product_check.prove(&mut Transcript::new(b"test"));
+
+        let sub_claim = ProductCheck::verify(&proof, F::from(40320), 3, &mut Transcript::new(b"test")).unwrap();
+
+        assert_eq!(sub_claim.challenges, point);
+        assert_eq!(sub_claim.claimed_eval, poly.eval_full(&point));
+    }
+
+    #[test]
+    fn test_product_check_wrong_product() {
+        let (proof, _) = ProductCheck::new(create_test_poly()).prove(&mut Transcript::new(b"test"));
+
+        assert_eq!(
+            ProductCheck::verify(&proof, F::from(40321), 3, &mut Transcript::new(b"test")),
+            Err(SumcheckError::Layer {
+                layer: 0,
+                error: Box::new(SumcheckError::FinalEvaluationMismatch {
+                    expected: F::from(40321),
+                    actual: F::from(40320),
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn test_product_check_tampered_evaluations() {
+        let (mut proof, _) = ProductCheck::new(create_test_poly()).prove(&mut Transcript::new(b"test"));
+        proof.layer_evaluations[1].0 += F::from(1);
+
+        assert!(matches!(
+            ProductCheck::verify(&proof, F::from(40320), 3, &mut Transcript::new(b"test")),
+            Err(SumcheckError::Layer { layer: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_product_check_final_claim_binds_polynomial() {
+        // Same product, different polynomial: the final claim does not match it
+        let (proof, _) = ProductCheck::new(create_test_poly()).prove(&mut Transcript::new(b"test"));
+        let sub_claim = ProductCheck::verify(&proof, F::from(40320), 3, &mut Transcript::new(b"test")).unwrap();
+
+        let mut other = create_test_poly();
+        other.evaluations.swap(0, 1);
+        assert_ne!(sub_claim.claimed_eval, other.eval_full(&sub_claim.challenges));
+    }
+}
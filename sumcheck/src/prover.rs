@@ -1,105 +1,155 @@
 use polynomials::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
-use ark_ff::{Field, PrimeField, BigInteger};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate, Write};
+use crate::error::ProofFormatError;
+use crate::serialization::{proof_from_bytes, proof_to_bytes};
+use fiat_shamir_transcript::absorb::Absorb;
 use fiat_shamir_transcript::transcript::Transcript;
-use crate::utils::*;
+use fiat_shamir_transcript::transcript_interface::TranscriptInterface;
 
 // SumCheckProof structure
 #[derive(Debug, Clone, PartialEq)]
 pub struct SumCheckProof<F: PrimeField> {
-    pub polynomial: MultiLinearPolynomial<F>,
     pub sum: F,
+    // The univariate g_i(X) of every round, as its evaluations at 0 and 1
     pub round_polynomials: Vec<MultiLinearPolynomial<F>>,
-    pub initial_round_polynomial: MultiLinearPolynomial<F>,
 }
 
 impl<F: PrimeField> SumCheckProof<F> {
+    /// The proof in the versioned byte format of [`crate::serialization`].
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut result = vec![];
-        result.extend(self.polynomial.to_bytes());
-        result.extend(self.sum.into_bigint().to_bytes_be());
-        for poly in &self.round_polynomials {
-            result.extend(poly.to_bytes());
+        proof_to_bytes(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofFormatError> {
+        proof_from_bytes(bytes)
+    }
+}
+
+impl<F: PrimeField> Absorb for SumCheckProof<F> {
+    fn write_transcript_bytes(&self, bytes: &mut Vec<u8>) {
+        self.sum.write_transcript_bytes(bytes);
+        self.round_polynomials.as_slice().write_transcript_bytes(bytes);
+    }
+}
+
+impl<F: PrimeField> CanonicalSerialize for SumCheckProof<F> {
+    fn serialize_with_mode<W: Write>(&self, mut writer: W, compress: Compress) -> Result<(), SerializationError> {
+        self.sum.serialize_with_mode(&mut writer, compress)?;
+        self.round_polynomials.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.sum.serialized_size(compress) + self.round_polynomials.serialized_size(compress)
+    }
+}
+
+// Every round polynomial is linear in a single variable, i.e. its evaluations at 0 and 1
+impl<F: PrimeField> Valid for SumCheckProof<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        if self.round_polynomials.iter().any(|poly| poly.variables != 1 || poly.evaluations.len() != 2) {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for SumCheckProof<F> {
+    fn deserialize_with_mode<R: Read>(mut reader: R, compress: Compress, validate: Validate) -> Result<Self, SerializationError> {
+        let proof = Self {
+            sum: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            round_polynomials: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+        };
+        if let Validate::Yes = validate {
+            proof.check()?;
         }
-        result.extend(self.initial_round_polynomial.to_bytes());
-        result
+        Ok(proof)
     }
 }
 
 
 // Prover structure and implementation
-#[derive(Clone, Default, Debug)]
-pub struct Prover<F: PrimeField> {
+#[derive(Clone, Debug)]
+pub struct Prover<F: PrimeField, T: TranscriptInterface = Transcript> {
     poly: MultiLinearPolynomial<F>,
     round_polynomials: Vec<MultiLinearPolynomial<F>>,
-    initial_round_polynomial: MultiLinearPolynomial<F>,
     sum: F,
-    transcript: Transcript,
+    transcript: T,
 }
 
 impl<F: PrimeField> Prover<F> {
     pub fn new(poly: MultiLinearPolynomial<F>) -> Self {
-        Self {
-            poly,
-            round_polynomials: Default::default(),
-            initial_round_polynomial: Default::default(),
-            sum: Default::default(),
-            transcript: Transcript::new(),
-        }
+        Self::new_with_transcript(poly, Transcript::new(b"multilinear_sumcheck"))
     }
 
     pub fn new_with_sum(poly: MultiLinearPolynomial<F>, sum: F) -> Self {
-        Self {
+        let mut prover = Self::new(poly);
+        prover.sum = sum;
+        prover
+    }
+}
+
+// The transcript needs its protocol label, so only the default one has a default
+impl<F: PrimeField> Default for Prover<F> {
+    fn default() -> Self {
+        Self::new(MultiLinearPolynomial::default())
+    }
+}
+
+impl<F: PrimeField, T: TranscriptInterface> Prover<F, T> {
+    pub fn new_with_transcript(poly: MultiLinearPolynomial<F>, transcript: T) -> Self {
+        let mut prover = Self {
             poly,
             round_polynomials: Default::default(),
-            initial_round_polynomial: Default::default(),
-            sum,
-            transcript: Transcript::new(),
-        }
+            sum: F::zero(),
+            transcript,
+        };
+        prover.calculate_sum();
+        prover
+    }
+
+    pub fn transcript(&self) -> &T {
+        &self.transcript
     }
 
     pub fn calculate_sum(&mut self) {
         self.sum = self.poly.evaluations.iter().sum();
     }
 
-    pub fn compute_initial_round_polynomial(&mut self) {
-        let number_of_round = self.poly.variables - 1; // We need the full set of variables for the boolean hypercube
-        let bh = boolean_hypercube::<F>(number_of_round);
-        let mut bh_partials: MultiLinearPolynomial<F> = MultiLinearPolynomial::zero(1);
-        
-        for bh_i in bh {
-            let current_partial = self.poly.partial_evaluations(bh_i, vec![1; number_of_round]); // Always evaluate at 0th index for the sumcheck
-                bh_partials += current_partial;
-            
-        }
-
-        self.transcript.append(&bh_partials.to_bytes());
-        self.initial_round_polynomial = bh_partials;
+    /// The round polynomial $g(X) = \sum_{x} f(X, x)$ of the table `evaluations`,
+    /// given by its evaluations at 0 and 1.
+    pub fn compute_round_polynomial(evaluations: &[F]) -> MultiLinearPolynomial<F> {
+        let (low, high) = evaluations.split_at(evaluations.len() / 2);
+        MultiLinearPolynomial::new(1, vec![low.iter().sum(), high.iter().sum()])
     }
 
-
-
+    /// Runs the sumcheck rounds, folding the evaluation table in place at every challenge
+    /// so the whole proof takes time linear in the size of the table.
     pub fn generate_sumcheck_proof(&mut self) -> SumCheckProof<F> {
-        self.compute_initial_round_polynomial();
-        let mut all_random_responses = Vec::new();
+        let mut evaluations = self.poly.evaluations.clone();
+        self.round_polynomials = Vec::with_capacity(self.poly.variables);
 
-        let mut current_poly = self.poly.clone();
+        self.transcript.absorb(b"sumcheck_claimed_sum", &self.sum);
 
         for _ in 0..self.poly.variables {
-            let verifier_random_response = self.transcript.sample_challenge();
-            all_random_responses.push(verifier_random_response);
+            let round_polynomial = Self::compute_round_polynomial(&evaluations);
+            self.transcript.absorb(b"sumcheck_round_polynomial", &round_polynomial);
+            self.round_polynomials.push(round_polynomial);
 
-            let next_poly = current_poly.partial_eval(verifier_random_response, 0);
-            self.transcript.append(&next_poly.to_bytes());
-            self.round_polynomials.push(next_poly.clone());
+            let verifier_random_response: F = self.transcript.compute_challenge_in_field(b"sumcheck_challenge");
 
-            current_poly = next_poly;
+            // Fix the first variable at the challenge: f(r, x) = f(0, x) + r * (f(1, x) - f(0, x))
+            let half = evaluations.len() / 2;
+            for i in 0..half {
+                let (low, high) = (evaluations[i], evaluations[i + half]);
+                evaluations[i] = low + verifier_random_response * (high - low);
+            }
+            evaluations.truncate(half);
         }
 
         SumCheckProof {
-            polynomial: self.poly.clone(),
-            round_polynomials: self.round_polynomials.clone(),
-            initial_round_polynomial: self.initial_round_polynomial.clone(),
             sum: self.sum,
+            round_polynomials: self.round_polynomials.clone(),
         }
     }
 
@@ -114,9 +164,8 @@ mod tests {
 
     use ark_test_curves::bls12_381::Fr;
 
-    #[test]
-    fn test_sum_calculation() {
-        let poly = MultiLinearPolynomial::new(
+    fn create_test_poly() -> MultiLinearPolynomial<Fr> {
+        MultiLinearPolynomial::new(
             3,
             vec![
                 Fr::from(0),
@@ -128,8 +177,12 @@ mod tests {
                 Fr::from(2),
                 Fr::from(4),
             ],
-        );
-        let mut prover = Prover::new(poly);
+        )
+    }
+
+    #[test]
+    fn test_sum_calculation() {
+        let mut prover = Prover::new(create_test_poly());
         prover.calculate_sum();
         assert_eq!(prover.sum, Fr::from(12));
     }
@@ -137,26 +190,80 @@ mod tests {
 
     #[test]
     fn test_compute_intial_round_poly() {
-        let poly = MultiLinearPolynomial::new(
-            3,
-            vec![
-                Fr::from(0),
-                Fr::from(0),
-                Fr::from(0),
-                Fr::from(2),
-                Fr::from(2),
-                Fr::from(2),
-                Fr::from(2),
-                Fr::from(4),
-            ],
-        );
-        let mut prover = Prover::new(poly);
-        prover.compute_initial_round_polynomial();
+        let poly = create_test_poly();
         assert_eq!(
-            prover.initial_round_polynomial.evaluations,
+            Prover::<Fr>::compute_round_polynomial(&poly.evaluations).evaluations,
             vec![Fr::from(2), Fr::from(10)]
         );
     }
 
+    #[test]
+    fn test_round_polynomials_match_partial_evaluations() {
+        let poly = create_test_poly();
+        let mut prover = Prover::new(poly.clone());
+        let proof = prover.generate_sumcheck_proof();
+
+        assert_eq!(proof.round_polynomials.len(), 3);
+        assert_eq!(proof.round_polynomials[0].evaluations, vec![Fr::from(2), Fr::from(10)]);
+
+        // Every round polynomial must match the polynomial fixed at the previous challenges
+        let mut current_poly = poly;
+        let mut transcript = Transcript::new(b"multilinear_sumcheck");
+        transcript.absorb(b"sumcheck_claimed_sum", &proof.sum);
+
+        for round_polynomial in &proof.round_polynomials {
+            assert_eq!(*round_polynomial, Prover::<Fr>::compute_round_polynomial(&current_poly.evaluations));
+            transcript.absorb(b"sumcheck_round_polynomial", round_polynomial);
+            current_poly = current_poly.partial_eval(transcript.compute_challenge_in_field(b"sumcheck_challenge"), 0);
+        }
+    }
+
+    // Returns the challenges 2, 3, 4, ... whatever data is submitted
+    #[derive(Default)]
+    struct MockTranscript {
+        counter: u64,
+    }
+
+    impl TranscriptInterface for MockTranscript {
+        fn new(_protocol_label: &'static [u8]) -> Self {
+            Self::default()
+        }
+
+        fn submit_data(&mut self, _label: &'static [u8], _input_data: &[u8]) {}
+
+        fn generate_challenge(&mut self, _label: &'static [u8]) -> [u8; 32] {
+            self.counter += 1;
+            let mut challenge = [0; 32];
+            challenge[31] = self.counter as u8 + 1;
+            challenge
+        }
+
+        fn compute_challenge_in_field<F: PrimeField>(&mut self, label: &'static [u8]) -> F {
+            F::from_be_bytes_mod_order(&self.generate_challenge(label))
+        }
+
+        fn fork(&self, _label: &'static [u8], _index: u64) -> Self {
+            Self { counter: self.counter }
+        }
+    }
+
+    #[test]
+    fn test_sumcheck_proof_with_mock_transcript() {
+        let poly = create_test_poly();
+        let mut prover = Prover::new_with_transcript(poly.clone(), MockTranscript::new(b"mock"));
+        let proof = prover.generate_sumcheck_proof();
+
+        let expected: Vec<_> = [vec![], vec![Fr::from(2)], vec![Fr::from(2), Fr::from(3)]]
+            .into_iter()
+            .map(|challenges| {
+                let num_challenges = challenges.len();
+                let current_poly = poly.partial_evaluations(challenges, vec![0; num_challenges]).unwrap();
+                Prover::<Fr>::compute_round_polynomial(&current_poly.evaluations)
+            })
+            .collect();
+
+        assert_eq!(proof.round_polynomials, expected);
+    }
+
 
-}
\ No newline at end of file
+}
@@ -0,0 +1,140 @@
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use crate::error::ProofFormatError;
+
+/// Bumped whenever the encoding of a proof changes, so old bytes are rejected
+/// instead of being misread.
+pub const PROOF_FORMAT_VERSION: u8 = 1;
+
+/// The version byte followed by the compressed canonical serialization of `proof`.
+pub fn proof_to_bytes<P: CanonicalSerialize>(proof: &P) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1 + proof.compressed_size());
+    bytes.push(PROOF_FORMAT_VERSION);
+    proof
+        .serialize_compressed(&mut bytes)
+        .expect("writing to a Vec cannot fail");
+    bytes
+}
+
+/// Reads a proof written by [`proof_to_bytes`], validating it and rejecting any trailing bytes.
+pub fn proof_from_bytes<P: CanonicalDeserialize>(bytes: &[u8]) -> Result<P, ProofFormatError> {
+    let mut reader = bytes;
+    let version = u8::deserialize_compressed(&mut reader)?;
+    if version != PROOF_FORMAT_VERSION {
+        return Err(ProofFormatError::UnsupportedVersion(version));
+    }
+
+    let proof = P::deserialize_compressed(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(ProofFormatError::TrailingBytes(reader.len()));
+    }
+
+    Ok(proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composed::composed_sumcheck::{ComposedSumcheck, ComposedSumcheckProof};
+    use crate::prover::{Prover, SumCheckProof};
+    use ark_ff::{BigInteger, PrimeField};
+    use ark_serialize::SerializationError;
+    use ark_test_curves::bls12_381::Fr as F;
+    use fiat_shamir_transcript::transcript::Transcript;
+    use polynomials::composed::composed_multilinear::ComposedMultilinearPolynomial;
+    use polynomials::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
+
+    fn create_sumcheck_proof() -> SumCheckProof<F> {
+        Prover::new(MultiLinearPolynomial::new(3, (1..=8).map(F::from).collect())).generate_sumcheck_proof()
+    }
+
+    fn create_composed_proof() -> ComposedSumcheckProof<F> {
+        let poly1 = MultiLinearPolynomial::new(2, vec![F::from(0), F::from(1), F::from(2), F::from(3)]);
+        let poly2 = MultiLinearPolynomial::new(2, vec![F::from(4), F::from(0), F::from(5), F::from(1)]);
+        let sumcheck = ComposedSumcheck::new(ComposedMultilinearPolynomial::new(vec![poly1, poly2]));
+        sumcheck.prove(&mut Transcript::new(b"test")).0
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let proof = create_sumcheck_proof();
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes[0], PROOF_FORMAT_VERSION);
+        assert_eq!(SumCheckProof::from_bytes(&bytes).unwrap(), proof);
+
+        let proof = create_composed_proof();
+        assert_eq!(ComposedSumcheckProof::from_bytes(&proof.to_bytes()).unwrap(), proof);
+    }
+
+    #[test]
+    fn test_round_trip_uncompressed() {
+        let proof = create_sumcheck_proof();
+        let mut bytes = Vec::new();
+        proof.serialize_uncompressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), proof.uncompressed_size());
+        assert_eq!(SumCheckProof::<F>::deserialize_uncompressed(bytes.as_slice()).unwrap(), proof);
+
+        let proof = create_composed_proof();
+        let mut bytes = Vec::new();
+        proof.serialize_uncompressed(&mut bytes).unwrap();
+        assert_eq!(ComposedSumcheckProof::<F>::deserialize_uncompressed(bytes.as_slice()).unwrap(), proof);
+    }
+
+    #[test]
+    fn test_wrong_version() {
+        let mut bytes = proof_to_bytes(&create_sumcheck_proof());
+        bytes[0] = PROOF_FORMAT_VERSION + 1;
+
+        assert!(matches!(
+            proof_from_bytes::<SumCheckProof<F>>(&bytes),
+            Err(ProofFormatError::UnsupportedVersion(version)) if version == PROOF_FORMAT_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn test_truncated_and_trailing_bytes() {
+        let bytes = proof_to_bytes(&create_composed_proof());
+
+        assert!(matches!(proof_from_bytes::<ComposedSumcheckProof<F>>(&[]), Err(ProofFormatError::Serialization(_))));
+        assert!(matches!(
+            proof_from_bytes::<ComposedSumcheckProof<F>>(&bytes[..bytes.len() - 1]),
+            Err(ProofFormatError::Serialization(_))
+        ));
+        assert!(matches!(
+            proof_from_bytes::<ComposedSumcheckProof<F>>(&[bytes.as_slice(), &[0]].concat()),
+            Err(ProofFormatError::TrailingBytes(1))
+        ));
+    }
+
+    #[test]
+    fn test_non_canonical_field_element() {
+        // The claimed sum directly follows the version byte; the modulus itself is out of range
+        let mut bytes = proof_to_bytes(&create_sumcheck_proof());
+        let modulus = F::MODULUS.to_bytes_le();
+        bytes[1..1 + modulus.len()].copy_from_slice(&modulus);
+
+        assert!(matches!(
+            proof_from_bytes::<SumCheckProof<F>>(&bytes),
+            Err(ProofFormatError::Serialization(SerializationError::InvalidData))
+        ));
+    }
+
+    #[test]
+    fn test_malformed_round_polynomials() {
+        let mut proof = create_sumcheck_proof();
+        proof.round_polynomials[1] = MultiLinearPolynomial::new(2, vec![F::from(1); 4]);
+        let bytes = proof_to_bytes(&proof);
+        assert!(matches!(
+            proof_from_bytes::<SumCheckProof<F>>(&bytes),
+            Err(ProofFormatError::Serialization(SerializationError::InvalidData))
+        ));
+        // Without validation the shape is not checked
+        assert_eq!(SumCheckProof::<F>::deserialize_compressed_unchecked(&bytes[1..]).unwrap(), proof);
+
+        let mut proof = create_composed_proof();
+        proof.round_polys[0].pop();
+        assert!(matches!(
+            proof_from_bytes::<ComposedSumcheckProof<F>>(&proof_to_bytes(&proof)),
+            Err(ProofFormatError::Serialization(SerializationError::InvalidData))
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use ark_ff::{BigInteger, PrimeField};
+use ark_ff::PrimeField;
 
 // Utility functions
 pub fn generate_pairs(len: usize) -> Vec<(usize, usize)> {
@@ -23,11 +23,63 @@ pub fn boolean_hypercube<F: PrimeField>(n: usize) -> Vec<Vec<F>> {
     result
 }
 
+/// $p(0) + p(1)$ for a round polynomial given by its evaluations at 0, 1, ..., d. A constant
+/// is sent as its single evaluation, which then counts twice.
+pub fn round_poly_sum<F: PrimeField>(evaluations: &[F]) -> F {
+    match evaluations {
+        [constant] => constant.double(),
+        _ => evaluations[0] + evaluations[1],
+    }
+}
+
+/// Evaluates at `point` the polynomial of degree < n given by its evaluations at 0, 1, ..., n - 1,
+/// with the barycentric form of Lagrange interpolation.
+pub fn evaluate_round_poly<F: PrimeField>(evaluations: &[F], point: F) -> F {
+    let n = evaluations.len();
+    let domain: Vec<F> = (0..n).map(|i| F::from(i as u64)).collect();
+    if let Some(i) = domain.iter().position(|x| *x == point) {
+        return evaluations[i];
+    }
+
+    // w_i = 1 / prod_{j != i} (i - j) and the differences point - i, inverted together
+    let mut denominators: Vec<F> = (0..n)
+        .map(|i| (0..n).filter(|&j| j != i).map(|j| domain[i] - domain[j]).product())
+        .chain(domain.iter().map(|x| point - x))
+        .collect();
+    ark_ff::batch_inversion(&mut denominators);
+    let (weights, inverse_differences) = denominators.split_at(n);
+
+    let vanishing: F = domain.iter().map(|x| point - x).product();
+    let sum: F = evaluations
+        .iter()
+        .zip(weights)
+        .zip(inverse_differences)
+        .map(|((y, w), d)| *y * w * d)
+        .sum();
+
+    vanishing * sum
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr;
+
+    #[test]
+    fn test_evaluate_round_poly() {
+        // 2 + 3x - x^2 + 4x^3 at 0, 1, 2, 3
+        let poly = |x: Fr| Fr::from(2) + Fr::from(3) * x - x * x + Fr::from(4) * x * x * x;
+        let evaluations: Vec<Fr> = (0..4).map(|i| poly(Fr::from(i))).collect();
+
+        for point in [Fr::from(0), Fr::from(2), Fr::from(7), -Fr::from(5)] {
+            assert_eq!(evaluate_round_poly(&evaluations, point), poly(point));
+        }
+    }
 
-pub fn vec_to_bytes<F: PrimeField>(poly: &Vec<F>) -> Vec<u8> {
-    let mut bytes = Vec::new();
-    for p in poly {
-        bytes.extend_from_slice(&p.into_bigint().to_bytes_be());
+    #[test]
+    fn test_round_poly_sum() {
+        assert_eq!(round_poly_sum(&[Fr::from(3), Fr::from(4), Fr::from(9)]), Fr::from(7));
+        assert_eq!(round_poly_sum(&[Fr::from(3)]), Fr::from(6));
     }
-    bytes
 }
@@ -1,75 +1,265 @@
-use polynomials::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
 use crate::prover::*;
+use crate::oracle::EvaluationOracle;
+use crate::error::SumcheckError;
 use ark_ff::PrimeField;
 use fiat_shamir_transcript::transcript::Transcript;
+use fiat_shamir_transcript::transcript_interface::TranscriptInterface;
 
-#[derive(Clone, Default, Debug)]
-pub struct Verifier<F: PrimeField> {
-    transcript: Transcript,
+/// What is left for the verifier to check once all the rounds pass:
+/// the summed polynomial evaluated at `challenges` must equal `claimed_eval`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubClaim<F: PrimeField> {
+    pub challenges: Vec<F>,
+    pub claimed_eval: F,
+}
+
+#[derive(Clone, Debug)]
+pub struct Verifier<F: PrimeField, T: TranscriptInterface = Transcript> {
+    transcript: T,
     phantom: std::marker::PhantomData<F>,
 }
 
 impl<F: PrimeField> Verifier<F> {
     pub fn new() -> Self {
+        Self::new_with_transcript(Transcript::new(b"multilinear_sumcheck"))
+    }
+}
+
+impl<F: PrimeField> Default for Verifier<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField, T: TranscriptInterface> Verifier<F, T> {
+    pub fn new_with_transcript(transcript: T) -> Self {
         Self {
-            transcript: Transcript::new(),
+            transcript,
             phantom: std::marker::PhantomData,
         }
     }
 
-    pub fn verify(&mut self, proof: &SumCheckProof<F>) -> bool {
-        let mut random_challenges = Vec::new();
+    pub fn transcript(&self) -> &T {
+        &self.transcript
+    }
 
-        let computed_sum = proof.initial_round_polynomial.eval_full(&vec![F::one()])
-            + proof.initial_round_polynomial.eval_full(&vec![F::zero()]);
+    /// Checks a proof over `num_variables` variables, leaving the final evaluation check to `oracle`.
+    pub fn verify<O: EvaluationOracle<F>>(
+        &mut self,
+        proof: &SumCheckProof<F>,
+        num_variables: usize,
+        oracle: &mut O,
+    ) -> Result<(), SumcheckError<F>> {
+        let sub_claim = self.verify_sub_claim(proof, num_variables)?;
 
-        if computed_sum != proof.sum {
-            println!("Computed sum does not match proof sum: {} != {}", computed_sum, proof.sum);
-            return false;
+        if !oracle.check_evaluation(&sub_claim.challenges, sub_claim.claimed_eval) {
+            return Err(SumcheckError::OracleRejected {
+                claimed_eval: sub_claim.claimed_eval,
+            });
         }
 
-        self.transcript.append(&proof.initial_round_polynomial.to_bytes());
+        Ok(())
+    }
 
-        for i in 0..proof.round_polynomials.len() {
-            let random_challenge = self.transcript.sample_challenge();
-            random_challenges.push(random_challenge);
+    /// Runs the round checks of a proof over `num_variables` variables and returns the
+    /// random point together with the value the polynomial is claimed to take there.
+    pub fn verify_sub_claim(&mut self, proof: &SumCheckProof<F>, num_variables: usize) -> Result<SubClaim<F>, SumcheckError<F>> {
+        if proof.round_polynomials.len() != num_variables {
+            return Err(SumcheckError::WrongNumberOfRounds {
+                expected: num_variables,
+                actual: proof.round_polynomials.len(),
+            });
+        }
+
+        let mut random_challenges = Vec::with_capacity(num_variables);
+        let mut claimed_sum = proof.sum;
+
+        self.transcript.absorb(b"sumcheck_claimed_sum", &proof.sum);
 
-            let current_round_poly = &proof.round_polynomials[i];
-            let prev_round_poly_eval = if i == 0 {
-                proof.initial_round_polynomial.eval_full(&vec![random_challenge])
-            } else {
-                proof.round_polynomials[i - 1].eval_full(&vec![random_challenge])
-            };
-
-            let current_round_poly_eval_sum = current_round_poly.eval_full(&vec![F::one()])
-                + current_round_poly.eval_full(&vec![F::zero()]);
-
-            if prev_round_poly_eval != current_round_poly_eval_sum {
-                println!(
-                    "Polynomial evaluation mismatch at round {}: {} != {}",
-                    i, prev_round_poly_eval, current_round_poly_eval_sum
-                );
-                return false;
+        for (i, round_poly) in proof.round_polynomials.iter().enumerate() {
+            // A linear polynomial in one variable, i.e. its evaluations at 0 and 1
+            if round_poly.variables != 1 || round_poly.evaluations.len() != 2 {
+                return Err(SumcheckError::RoundPolynomialLength {
+                    round: i,
+                    expected: 2,
+                    actual: round_poly.evaluations.len(),
+                });
             }
 
-            self.transcript.append(&current_round_poly.to_bytes());
+            let round_poly_sum = round_poly.evaluations[0] + round_poly.evaluations[1];
+            if round_poly_sum != claimed_sum {
+                return Err(SumcheckError::RoundSumMismatch {
+                    round: i,
+                    expected: claimed_sum,
+                    actual: round_poly_sum,
+                });
+            }
+
+            self.transcript.absorb(b"sumcheck_round_polynomial", round_poly);
+            let random_challenge: F = self.transcript.compute_challenge_in_field(b"sumcheck_challenge");
+            random_challenges.push(random_challenge);
+
+            claimed_sum = round_poly.eval_full(&[random_challenge]);
         }
 
-        let final_challenge = self.transcript.sample_challenge();
-        random_challenges.push(final_challenge);
+        Ok(SubClaim {
+            challenges: random_challenges,
+            claimed_eval: claimed_sum,
+        })
+    }
+}
 
-        let last_poly_eval = proof.round_polynomials.last().unwrap()
-            .eval_full(&vec![final_challenge]);
-        let main_poly_eval = proof.polynomial.eval_full(&random_challenges);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::DeferredOracle;
+    use fiat_shamir_transcript::transcript::Sha256Transcript;
+    use fiat_shamir_transcript::poseidon::PoseidonTranscript;
+    use fiat_shamir_transcript::recording::{RecordingTranscript, TranscriptEvent};
+    use polynomials::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
+    use ark_test_curves::bls12_381::Fr;
 
-        if last_poly_eval != main_poly_eval {
-            println!(
-                "Final polynomial evaluation mismatch: {} != {}",
-                last_poly_eval, main_poly_eval
-            );
-            return false;
-        }
+    fn create_test_poly() -> MultiLinearPolynomial<Fr> {
+        MultiLinearPolynomial::new(
+            3,
+            vec![
+                Fr::from(0),
+                Fr::from(0),
+                Fr::from(0),
+                Fr::from(2),
+                Fr::from(2),
+                Fr::from(2),
+                Fr::from(2),
+                Fr::from(4),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_sum_check_proof() {
+        let mut prover = Prover::new(create_test_poly());
+        let proof = prover.generate_sumcheck_proof();
+
+        assert!(Verifier::new().verify(&proof, 3, &mut create_test_poly()).is_ok());
+    }
+
+    #[test]
+    fn test_default_uses_the_default_transcript() {
+        let mut prover = Prover::<Fr>::default();
+        let proof = prover.generate_sumcheck_proof();
+        assert_eq!(proof.sum, Fr::from(0));
+
+        let proof = Prover::new(create_test_poly()).generate_sumcheck_proof();
+        assert!(Verifier::<Fr>::default().verify(&proof, 3, &mut create_test_poly()).is_ok());
+    }
+
+    #[test]
+    fn test_sum_check_proof_with_other_hash() {
+        let mut prover = Prover::new_with_transcript(create_test_poly(), Sha256Transcript::new(b"multilinear_sumcheck"));
+        let proof = prover.generate_sumcheck_proof();
+
+        let mut verifier = Verifier::new_with_transcript(Sha256Transcript::new(b"multilinear_sumcheck"));
+        assert!(verifier.verify(&proof, 3, &mut create_test_poly()).is_ok());
+
+        // The Keccak transcript derives other challenges, so the rounds no longer line up
+        assert!(Verifier::new().verify(&proof, 3, &mut create_test_poly()).is_err());
+    }
+
+    #[test]
+    fn test_sum_check_proof_with_poseidon() {
+        let mut prover = Prover::new_with_transcript(create_test_poly(), PoseidonTranscript::<Fr>::new(b"multilinear_sumcheck"));
+        let proof = prover.generate_sumcheck_proof();
+
+        let mut verifier = Verifier::new_with_transcript(PoseidonTranscript::<Fr>::new(b"multilinear_sumcheck"));
+        assert!(verifier.verify(&proof, 3, &mut create_test_poly()).is_ok());
+    }
+
+    #[test]
+    fn test_sub_claim() {
+        let poly = create_test_poly();
+        let mut prover = Prover::new(poly.clone());
+        let proof = prover.generate_sumcheck_proof();
+
+        let sub_claim = Verifier::new().verify_sub_claim(&proof, 3).unwrap();
+
+        assert_eq!(sub_claim.challenges.len(), 3);
+        assert_eq!(sub_claim.claimed_eval, poly.eval_full(&sub_claim.challenges));
+        assert_eq!(
+            Verifier::new().verify_sub_claim(&proof, 2),
+            Err(SumcheckError::WrongNumberOfRounds { expected: 2, actual: 3 })
+        );
+    }
+
+    #[test]
+    fn test_sum_check_proof_deferred() {
+        let poly = create_test_poly();
+        let mut prover = Prover::new(poly.clone());
+        let proof = prover.generate_sumcheck_proof();
+
+        let mut oracle = DeferredOracle::new();
+        assert!(Verifier::new().verify(&proof, 3, &mut oracle).is_ok());
+
+        assert_eq!(oracle.claims.len(), 1);
+        assert_eq!(oracle.claims[0].claimed_eval, poly.eval_full(&oracle.claims[0].challenges));
+    }
+
+    #[test]
+    fn test_sum_check_proof_wrong_polynomial() {
+        let mut prover = Prover::new(create_test_poly());
+        let proof = prover.generate_sumcheck_proof();
+
+        let mut other_poly = create_test_poly();
+        other_poly.evaluations[0] += Fr::from(1);
+        other_poly.evaluations[7] -= Fr::from(1);
+
+        assert!(matches!(
+            Verifier::new().verify(&proof, 3, &mut other_poly),
+            Err(SumcheckError::OracleRejected { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sum_check_proof_wrong_sum() {
+        let mut prover = Prover::new_with_sum(create_test_poly(), Fr::from(13));
+        let proof = prover.generate_sumcheck_proof();
+
+        // The honest sum is 12
+        assert_eq!(
+            Verifier::new().verify(&proof, 3, &mut create_test_poly()),
+            Err(SumcheckError::RoundSumMismatch {
+                round: 0,
+                expected: Fr::from(13),
+                actual: Fr::from(12),
+            })
+        );
+    }
+
+    #[test]
+    fn test_sum_check_proof_tampered_round() {
+        let mut prover = Prover::new(create_test_poly());
+        let mut proof = prover.generate_sumcheck_proof();
+        proof.round_polynomials[1].evaluations[0] += Fr::from(1);
+        proof.round_polynomials[1].evaluations[1] -= Fr::from(1);
+
+        assert!(matches!(
+            Verifier::new().verify(&proof, 3, &mut create_test_poly()),
+            Err(SumcheckError::RoundSumMismatch { round: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_recorded_transcripts_diverge_at_tampered_round() {
+        let mut prover = Prover::new_with_transcript(create_test_poly(), RecordingTranscript::<Transcript>::new(b"multilinear_sumcheck"));
+        let mut proof = prover.generate_sumcheck_proof();
+        proof.round_polynomials[1].evaluations[0] += Fr::from(1);
+        proof.round_polynomials[1].evaluations[1] -= Fr::from(1);
+
+        let mut verifier = Verifier::new_with_transcript(RecordingTranscript::<Transcript>::new(b"multilinear_sumcheck"));
+        assert!(verifier.verify(&proof, 3, &mut create_test_poly()).is_err());
 
-        true
+        // dom-sep, claimed sum, then an absorb and a squeeze per round
+        let divergence = prover.transcript().recording().first_divergence(verifier.transcript().recording()).unwrap();
+        assert_eq!(divergence.index, 4);
+        assert!(matches!(divergence.left, Some(TranscriptEvent::Absorb { ref label, .. }) if label == b"sumcheck_round_polynomial"));
     }
 }
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use ark_ff::PrimeField;
+use polynomials::multilinear_pol::eq_poly::{eq_eval, eq_poly};
+use polynomials::virtual_poly::virtual_polynomial::VirtualPolynomial;
+use crate::composed::composed_sumcheck::{ComposedSumcheck, ComposedSumcheckProof};
+use crate::oracle::DeferredOracle;
+use crate::verifier::SubClaim;
+use crate::error::SumcheckError;
+use fiat_shamir_transcript::transcript_interface::TranscriptInterface;
+
+/// Proves that `poly` vanishes on the whole boolean hypercube: for a random `r`,
+/// `sum_x poly(x) * eq(x, r)` is zero, which is a composed sumcheck claim.
+#[derive(Debug, Clone)]
+pub struct ZeroCheck<F: PrimeField> {
+    pub poly: VirtualPolynomial<F>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ZeroCheckProof<F: PrimeField> {
+    // The sumcheck of poly(x) * eq(x, r), of degree one more than `poly`
+    pub sumcheck_proof: ComposedSumcheckProof<F>,
+}
+
+impl<F: PrimeField> ZeroCheck<F> {
+    pub fn new(poly: impl Into<VirtualPolynomial<F>>) -> Self {
+        Self { poly: poly.into() }
+    }
+
+    /// Returns the proof and the random point of the sumcheck.
+    pub fn prove<T: TranscriptInterface>(&self, transcript: &mut T) -> (ZeroCheckProof<F>, Vec<F>) {
+        let r: Vec<F> = transcript.compute_challenges_in_field(b"zerocheck_r", self.poly.num_variables);
+
+        let sumcheck = ComposedSumcheck {
+            poly: Self::mul_by_eq(&self.poly, &r),
+            sum: F::zero(),
+        };
+        let (sumcheck_proof, challenges) = sumcheck.prove(transcript);
+
+        (ZeroCheckProof { sumcheck_proof }, challenges)
+    }
+
+    /// Checks a proof for a polynomial of `max_degree` over `num_variables` variables.
+    /// On success, returns the point and the value `poly` must take there, which the
+    /// caller still has to check (e.g. against commitments to the MLEs).
+    pub fn verify<T: TranscriptInterface>(
+        proof: &ZeroCheckProof<F>,
+        num_variables: usize,
+        max_degree: usize,
+        transcript: &mut T,
+    ) -> Result<SubClaim<F>, SumcheckError<F>> {
+        let r: Vec<F> = transcript.compute_challenges_in_field(b"zerocheck_r", num_variables);
+
+        let mut oracle = DeferredOracle::new();
+        ComposedSumcheck::verify_with_oracle(
+            &proof.sumcheck_proof,
+            F::zero(),
+            num_variables,
+            max_degree + 1,
+            &mut oracle,
+            transcript,
+        )?;
+
+        // The sumcheck ends on poly(point) * eq(point, r), and eq(point, r) is known to the verifier
+        let sub_claim = oracle.claims.pop().expect("the deferred oracle records the final claim");
+        let inverse = eq_eval(&sub_claim.challenges, &r).inverse().ok_or(SumcheckError::EqVanishes)?;
+
+        Ok(SubClaim {
+            claimed_eval: sub_claim.claimed_eval * inverse,
+            challenges: sub_claim.challenges,
+        })
+    }
+
+    // Appends eq(x, r) to every product of `poly`
+    fn mul_by_eq(poly: &VirtualPolynomial<F>, r: &[F]) -> VirtualPolynomial<F> {
+        let mut result = poly.clone();
+        let eq_index = result.add_mle(Arc::new(eq_poly(r)));
+        for (_, indices) in result.products.iter_mut() {
+            indices.push(eq_index);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr as F;
+    use fiat_shamir_transcript::transcript::Transcript;
+    use polynomials::multilinear_pol::multilinear_poly::MultiLinearPolynomial;
+
+    fn mle(evaluations: &[i64]) -> Arc<MultiLinearPolynomial<F>> {
+        Arc::new(MultiLinearPolynomial::new(2, evaluations.iter().map(|&e| F::from(e)).collect()))
+    }
+
+    // A * B - C, which vanishes when C is the product of A and B
+    fn constraint(c: Arc<MultiLinearPolynomial<F>>) -> VirtualPolynomial<F> {
+        let mut poly = VirtualPolynomial::new(2);
+        poly.add_product(F::from(1), vec![mle(&[1, 2, 3, 4]), mle(&[5, 0, 2, 7])]);
+        poly.add_product(-F::from(1), vec![c]);
+        poly
+    }
+
+    #[test]
+    fn test_zerocheck() {
+        let poly = constraint(mle(&[5, 0, 6, 28]));
+        let (proof, challenges) = ZeroCheck::new(poly.clone()).prove(&mut Transcript::new(b"test"));
+
+        let sub_claim = ZeroCheck::verify(&proof, 2, 2, &mut Transcript::new(b"test")).unwrap();
+
+        assert_eq!(sub_claim.challenges, challenges);
+        assert_eq!(sub_claim.claimed_eval, poly.evaluate(&challenges));
+        assert_eq!(proof.sumcheck_proof.round_polys[0].len(), 4);
+    }
+
+    #[test]
+    fn test_zerocheck_non_vanishing() {
+        // Off by one at two points, so the sum over the hypercube is still zero
+        let poly = constraint(mle(&[6, 0, 6, 27]));
+        assert_eq!(poly.evaluations().iter().sum::<F>(), F::from(0));
+
+        let (proof, _) = ZeroCheck::new(poly).prove(&mut Transcript::new(b"test"));
+        assert!(matches!(
+            ZeroCheck::verify(&proof, 2, 2, &mut Transcript::new(b"test")),
+            Err(SumcheckError::RoundSumMismatch { round: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_zerocheck_wrong_degree() {
+        let poly = constraint(mle(&[5, 0, 6, 28]));
+        let (proof, _) = ZeroCheck::new(poly).prove(&mut Transcript::new(b"test"));
+
+        assert_eq!(
+            ZeroCheck::verify(&proof, 2, 3, &mut Transcript::new(b"test")),
+            Err(SumcheckError::RoundPolynomialLength { round: 0, expected: 5, actual: 4 })
+        );
+    }
+}